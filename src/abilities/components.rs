@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::persistence::resources::PersistentState;
+
+/// Abilities that are earned over the course of the game.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Ability {
+    DoubleJump,
+    Kick,
+    Slide,
+    Shot,
+    Grapple,
+    LedgeGrab,
+    Climb,
+    Swim,
+}
+
+impl Ability {
+    pub const ALL: [Ability; 8] = [
+        Ability::DoubleJump,
+        Ability::Kick,
+        Ability::Slide,
+        Ability::Shot,
+        Ability::Grapple,
+        Ability::LedgeGrab,
+        Ability::Climb,
+        Ability::Swim,
+    ];
+
+    /// Returns the flag used to remember this ability in the `PersistentState`.
+    pub fn flag(&self) -> String {
+        format!("ability:{self:?}")
+    }
+}
+
+/// The abilities a character has unlocked, behaviors tied to an ability stay inert until it is.
+///
+/// Characters without this component are treated as having every ability.
+#[derive(Component)]
+pub struct Abilities {
+    unlocked: HashSet<Ability>,
+}
+
+impl Abilities {
+    pub fn new(unlocked: &[Ability]) -> Self {
+        Self {
+            unlocked: unlocked.iter().copied().collect(),
+        }
+    }
+
+    /// Starts from `unlocked` and adds everything remembered in `state`.
+    pub fn restore(unlocked: &[Ability], state: &PersistentState) -> Self {
+        let mut abilities = Self::new(unlocked);
+        abilities.unlocked.extend(
+            Ability::ALL
+                .into_iter()
+                .filter(|ability| state.check(&ability.flag())),
+        );
+        abilities
+    }
+
+    pub fn has(&self, ability: Ability) -> bool {
+        self.unlocked.contains(&ability)
+    }
+
+    /// Unlocks `ability`, returning false if it already was.
+    pub fn unlock(&mut self, ability: Ability) -> bool {
+        self.unlocked.insert(ability)
+    }
+
+    /// Checks if a character with `abilities` may use `ability`.
+    pub fn allows(abilities: Option<&Self>, ability: Ability) -> bool {
+        abilities.map_or(true, |abilities| abilities.has(ability))
+    }
+}
+
+/// An item that grants its ability to the character that touches it.
+#[derive(Component)]
+pub struct AbilityPickup(pub Ability);
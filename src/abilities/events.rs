@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+use super::components::Ability;
+
+#[derive(Event)]
+pub struct AbilityUnlocked {
+    pub owner: Entity,
+    pub ability: Ability,
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::system_sets::GameplaySet;
+
+use self::{
+    events::AbilityUnlocked,
+    systems::{collect_ability_pickups, persist_unlocked_abilities},
+};
+
+pub mod components;
+pub mod events;
+mod systems;
+
+pub struct AbilitiesPlugin;
+
+impl Plugin for AbilitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AbilityUnlocked>().add_systems(
+            FixedUpdate,
+            (collect_ability_pickups, persist_unlocked_abilities)
+                .chain()
+                .in_set(GameplaySet::HitResolution),
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    collision_groups::{CollisionGroup, PLAYER},
+    persistence::resources::PersistentState,
+    player::components::Player,
+};
+
+use super::{
+    components::{Abilities, AbilityPickup},
+    events::AbilityUnlocked,
+};
+
+/// Grants pickups to whoever's hurtbox overlaps them.
+pub fn collect_ability_pickups(
+    mut commands: Commands,
+    q_pickup: Query<(Entity, &Transform, &Collider, &AbilityPickup)>,
+    q_parent: Query<&Parent>,
+    mut q_abilities: Query<&mut Abilities>,
+    mut unlocked: EventWriter<AbilityUnlocked>,
+    spatial_query: SpatialQuery,
+) {
+    for (pickup, transform, collider, &AbilityPickup(ability)) in q_pickup.iter() {
+        // Hurtboxes are children of the character they belong to
+        let Some(owner) = spatial_query
+            .shape_intersections(
+                collider,
+                transform.translation.xy(),
+                0.,
+                CollisionGroup::filter(PLAYER),
+            )
+            .into_iter()
+            .filter_map(|hurtbox| q_parent.get(hurtbox).ok())
+            .map(Parent::get)
+            .find(|owner| q_abilities.contains(*owner))
+        else {
+            continue;
+        };
+
+        let Ok(mut abilities) = q_abilities.get_mut(owner) else {
+            continue;
+        };
+
+        if abilities.unlock(ability) {
+            unlocked.send(AbilityUnlocked { owner, ability });
+        }
+        commands.entity(pickup).despawn_recursive();
+    }
+}
+
+pub fn persist_unlocked_abilities(
+    mut unlocked: EventReader<AbilityUnlocked>,
+    q_player: Query<(), With<Player>>,
+    mut state: ResMut<PersistentState>,
+) {
+    for event in unlocked.read() {
+        if q_player.contains(event.owner) {
+            state.set(event.ability.flag());
+        }
+    }
+}
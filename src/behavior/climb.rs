@@ -0,0 +1,174 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::abilities::components::{Abilities, Ability};
+use crate::characters::components::Body;
+use crate::collision_groups::{CollisionGroup, CLIMBABLE};
+use crate::input::blocker::Blocker;
+use crate::input::buffer::InputBuffer;
+use crate::input::directions::InputDirection;
+use crate::shape_intersections::ShapeIntersections;
+use crate::state::grounded::Grounded;
+use crate::system_sets::BehaviorSet;
+use crate::world::components::Climbable;
+
+use super::crouch::Crouch;
+use super::jump::Jump;
+use super::swim::swimming_behavior_player;
+use super::BehaviorInput;
+
+#[derive(Component)]
+pub struct Climb {
+    stage: Stage,
+    speed: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    Climbing { climbable: Entity },
+}
+
+impl Climb {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            speed,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        matches!(self.stage, Stage::Climbing { .. })
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        self.stage = stage;
+    }
+}
+
+pub fn climbing_behavior_player(
+    mut q_player: Query<(
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &mut Jump,
+        &BehaviorInput<Jump>,
+        &mut Climb,
+        &BehaviorInput<Climb, InputDirection>,
+        &BehaviorInput<Crouch, InputDirection>,
+        Option<&Crouch>,
+        &Body,
+        &Grounded,
+        Option<&Abilities>,
+    )>,
+    q_climbable: Query<(&Transform, &Climbable), Without<Climb>>,
+    mut shape_intersections: ShapeIntersections,
+) {
+    for (
+        mut transform,
+        mut vel,
+        mut buffer,
+        mut jump,
+        jump_input,
+        mut state,
+        binding,
+        crouch_input,
+        o_crouch,
+        body,
+        grounded,
+        o_abilities,
+    ) in q_player.iter_mut()
+    {
+        // Directions are blocked for other behaviors while climbing, so we read the raw frame
+        let frame = buffer.this_frame();
+
+        match state.stage {
+            Stage::Dormant
+                if !o_crouch.is_some_and(Crouch::check)
+                    && Abilities::allows(o_abilities, Ability::Climb)
+                    && buffer.any(vec![binding.get(), crouch_input.get()]) =>
+            {
+                let Some(&climbable) = shape_intersections
+                    .shape_intersections(
+                        &Collider::rectangle(body.width / 2., body.height),
+                        transform.translation.xy(),
+                        0.,
+                        CollisionGroup::filter(CLIMBABLE),
+                    )
+                    .first()
+                else {
+                    continue;
+                };
+
+                // Climbing down from the floor is crouching unless the climbable goes below us
+                let Ok((climbable_transform, climbable_data)) = q_climbable.get(climbable) else {
+                    continue;
+                };
+                let (_, bottom) = climbable_data.ends(climbable_transform.translation.xy());
+                let feet = transform.translation.y - body.height / 2.;
+                if grounded.check() && buffer.is(crouch_input.get()) && bottom >= feet {
+                    continue;
+                }
+
+                transform.translation.x = climbable_transform.translation.x;
+                *vel = LinearVelocity::ZERO;
+
+                jump.suspend_gravity();
+                jump.reset_air_jump();
+                buffer.block(Blocker::directions());
+                state.set_stage(Stage::Climbing { climbable });
+            }
+            Stage::Climbing { .. } if frame.just_pressed(jump_input.get()) => {
+                // Jump takes it from here, it runs after us and sees the press
+                jump.resume_gravity();
+                buffer.clear_blocker();
+                state.set_stage(Stage::Dormant);
+            }
+            Stage::Climbing { climbable } => {
+                let Ok((climbable_transform, climbable_data)) = q_climbable.get(climbable) else {
+                    jump.resume_gravity();
+                    buffer.clear_blocker();
+                    state.set_stage(Stage::Dormant);
+                    continue;
+                };
+
+                let (top, bottom) = climbable_data.ends(climbable_transform.translation.xy());
+                let feet = transform.translation.y - body.height / 2.;
+                let y_input = frame.y();
+
+                let off_top = y_input > 0. && feet >= top;
+                let off_bottom =
+                    y_input < 0. && (grounded.check() || transform.translation.y < bottom);
+
+                if off_top || off_bottom {
+                    *vel = LinearVelocity::ZERO;
+                    jump.resume_gravity();
+                    buffer.clear_blocker();
+                    state.set_stage(Stage::Dormant);
+                    continue;
+                }
+
+                transform.translation.x = climbable_transform.translation.x;
+                vel.x = 0.;
+                vel.y = if y_input.abs() > 0.2 {
+                    state.speed * y_input.signum()
+                } else {
+                    0.
+                };
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct ClimbBehavior;
+
+impl Plugin for ClimbBehavior {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            climbing_behavior_player
+                .in_set(BehaviorSet::Traversal)
+                .after(swimming_behavior_player),
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::input::buffer::InputBuffer;
+use crate::input::inputs::Inputs;
+use crate::system_sets::BehaviorSet;
+
+use super::crouch::Crouch;
+use super::slide::sliding_handler_player;
+
+/// Slower movement while crouched, using the crouch collider.
+#[derive(Component)]
+pub struct Crawl {
+    stage: Stage,
+    slowing_factor: f32,
+    max_speed: f32,
+    acceleration_factor: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    Crawling,
+}
+
+impl Crawl {
+    pub fn new(slowing_factor: f32, max_speed: f32, acceleration_factor: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            slowing_factor,
+            max_speed,
+            acceleration_factor,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        matches!(self.stage, Stage::Crawling)
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        self.stage = stage;
+    }
+}
+
+pub fn crawling_behavior_player(
+    mut q_player: Query<(&mut LinearVelocity, &Crouch, &InputBuffer, &mut Crawl)>,
+    time: Res<Time>,
+) {
+    for (mut vel, crouching, buffer, mut state) in q_player.iter_mut() {
+        // Sliding blocks every input, so it keeps its speed until it settles
+        if !crouching.check() || buffer.blocked(Inputs::Directional) {
+            state.set_stage(Stage::Dormant);
+            continue;
+        }
+
+        let x_input = buffer.this_frame().x();
+        let delta = time.delta_seconds();
+
+        // Walk still slows us down when there's no input, crawling only has to move us
+        if x_input.abs() <= 0.2 {
+            state.set_stage(Stage::Dormant);
+            continue;
+        }
+        state.set_stage(Stage::Crawling);
+
+        vel.x += x_input * state.max_speed * state.acceleration_factor * delta;
+
+        // Coming out of a slide or a run is faster than a crawl, so ease down to it
+        if vel.x.abs() > state.max_speed {
+            vel.x -= (vel.x - state.max_speed.copysign(vel.x)) * state.slowing_factor * delta;
+        }
+    }
+}
+
+pub struct CrawlBehavior;
+
+impl Plugin for CrawlBehavior {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            crawling_behavior_player
+                .in_set(BehaviorSet::Stance)
+                .after(sliding_handler_player),
+        );
+    }
+}
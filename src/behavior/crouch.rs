@@ -7,9 +7,12 @@ use crate::state::grounded::Grounded;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::player::components::{Body, Player};
+use crate::characters::components::Body;
+use crate::system_sets::BehaviorSet;
 
+use super::drop_through::drop_through_behavior_player;
 use super::slide::Slide;
+use super::BehaviorInput;
 
 #[derive(Component)]
 pub struct Crouch {
@@ -65,32 +68,81 @@ impl Crouch {
             Stage::Crouching { .. } => true,
         }
     }
+
+    /// Checks that the upper half of a standing `body` centered on `position` is clear of the environment.
+    pub fn has_standing_clearance(
+        shape_intersections: &mut ShapeIntersections,
+        body: &Body,
+        position: Vec2,
+    ) -> bool {
+        shape_intersections
+            .shape_intersections(
+                &Collider::rectangle(body.width / 2., body.height / 2.),
+                position + Vec2::new(0., body.height / 4.),
+                0.,
+                CollisionGroup::filter(ENVIRONMENT),
+            )
+            .is_empty()
+    }
+
+    /// Checks for a gap just ahead of a standing `body` that is too low to walk into, but high enough to crawl through.
+    pub fn has_low_gap_ahead(
+        shape_intersections: &mut ShapeIntersections,
+        body: &Body,
+        position: Vec2,
+        direction: f32,
+    ) -> bool {
+        let ahead = position + Vec2::new(body.width * direction, 0.);
+
+        // Shrunk a little so the floor under the gap doesn't count
+        let crawl_space_clear = shape_intersections
+            .shape_intersections(
+                &Collider::rectangle(body.width / 2., body.height / 2. - 2.),
+                ahead - Vec2::new(0., body.height / 4.),
+                0.,
+                CollisionGroup::filter(ENVIRONMENT),
+            )
+            .is_empty();
+
+        crawl_space_clear && !Crouch::has_standing_clearance(shape_intersections, body, ahead)
+    }
 }
 
 pub fn crouching_behavior_player(
-    mut q_player: Query<
-        (
-            Entity,
-            Option<&Slide>,
-            &Grounded,
-            &mut Body,
-            &mut Crouch,
-            &mut InputBuffer,
-        ),
-        With<Player>,
-    >,
+    mut q_player: Query<(
+        Entity,
+        Option<&Slide>,
+        &Grounded,
+        &mut Body,
+        &mut Crouch,
+        &mut InputBuffer,
+        &BehaviorInput<Crouch, InputDirection>,
+    )>,
     mut collision_params: ParamSet<(Query<&mut CollisionLayers>, ShapeIntersections)>,
     q_transform: Query<&Transform>,
     mut commands: Commands,
 ) {
-    for (entity, slide, grounded, mut body, mut state, input) in q_player.iter_mut() {
+    for (entity, slide, grounded, mut body, mut state, input, binding) in q_player.iter_mut() {
+        // Walking into a low tunnel crouches on its own, and we stay down while still heading into one,
+        // standing back up once there's room again
+        let x_input = input.this_frame().x();
+        let low_gap_ahead = x_input.abs() > 0.2
+            && q_transform.get(entity).is_ok_and(|transform| {
+                Crouch::has_low_gap_ahead(
+                    &mut collision_params.p1(),
+                    &body,
+                    transform.translation.xy(),
+                    x_input.signum(),
+                )
+            });
+
         match &state.stage {
-            Stage::Standing if (input.is(InputDirection::Down) && grounded.check()) => {
+            Stage::Standing if (input.is(binding.get()) || low_gap_ahead) && grounded.check() => {
                 let mut q_collision_group = collision_params.p0();
 
                 let Ok(mut body_collision_group) = q_collision_group.get_mut(body.collider_ref)
                 else {
-                    return;
+                    continue;
                 };
 
                 *body_collision_group = CollisionGroup::INACTIVE;
@@ -107,23 +159,20 @@ pub fn crouching_behavior_player(
                 );
             }
             Stage::Crouching { collider_storage }
-                if !input.is(InputDirection::Down) && !slide.is_some_and(Slide::check) =>
+                if !input.is(binding.get())
+                    && !slide.is_some_and(Slide::check)
+                    && !low_gap_ahead =>
             {
                 let Ok(transform) = q_transform.get(entity) else {
-                    return;
+                    continue;
                 };
 
-                if !collision_params
-                    .p1()
-                    .shape_intersections(
-                        &Collider::rectangle(body.width / 2., body.height / 2.),
-                        transform.translation.xy() + Vec2::new(0., body.height / 4.),
-                        0.,
-                        CollisionGroup::filter(ENVIRONMENT),
-                    )
-                    .is_empty()
-                {
-                    return;
+                if !Crouch::has_standing_clearance(
+                    &mut collision_params.p1(),
+                    &body,
+                    transform.translation.xy(),
+                ) {
+                    continue;
                 }
 
                 commands.entity(body.collider_ref).despawn_recursive();
@@ -132,7 +181,7 @@ pub fn crouching_behavior_player(
 
                 let mut q_collision_layers = collision_params.p0();
                 let Ok(mut collision_group) = q_collision_layers.get_mut(body.collider_ref) else {
-                    return;
+                    continue;
                 };
                 *collision_group = CollisionGroup::COLLIDER;
 
@@ -147,6 +196,11 @@ pub struct CrouchBehavior;
 
 impl Plugin for CrouchBehavior {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, crouching_behavior_player);
+        app.add_systems(
+            FixedUpdate,
+            crouching_behavior_player
+                .in_set(BehaviorSet::Stance)
+                .after(drop_through_behavior_player),
+        );
     }
 }
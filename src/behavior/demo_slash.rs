@@ -3,21 +3,96 @@ use std::time::Duration;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::collision_groups::ENEMY;
 use crate::input::blocker::Blocker;
 use crate::input::buffer::InputBuffer;
+use crate::input::directions::InputDirection;
 use crate::shape_intersections::ShapeIntersections;
+use crate::state::energy::Energy;
 use crate::state::facing_direction::FacingDirection;
 use crate::state::grounded::Grounded;
-use crate::{collision_groups::*, player::components::Player};
+use crate::{characters::components::Faction, collision_groups::*, system_sets::BehaviorSet};
 
-use super::BehaviorInput;
+use super::jump::Jump;
+use super::{AttackKind, BehaviorInput, Struck};
+
+/// A rectangular hit area, offset from the attacker as if they were facing right.
+#[derive(Clone, Copy)]
+pub struct Hitbox {
+    offset: Vec2,
+    size: Vec2,
+}
+
+impl Hitbox {
+    pub fn new(offset: Vec2, size: Vec2) -> Self {
+        Self { offset, size }
+    }
+
+    /// Returns the world space center of the hitbox for an attacker at `origin` facing `direction`.
+    pub fn position(&self, origin: Vec2, direction: f32) -> Vec2 {
+        origin + Vec2::new(self.offset.x * direction, self.offset.y)
+    }
+
+    pub fn collider(&self) -> Collider {
+        Collider::rectangle(self.size.x, self.size.y)
+    }
+}
+
+/// A single attack in a slash combo.
+#[derive(Clone)]
+pub struct SlashAttack {
+    windup: Duration,
+    active: Duration,
+    settle: Duration,
+    // forward speed given when the attack becomes active on the ground
+    momentum: f32,
+    damage: f32,
+    hitboxes: Vec<Hitbox>,
+}
+
+impl SlashAttack {
+    pub fn new(
+        windup: f32,
+        active: f32,
+        settle: f32,
+        momentum: f32,
+        damage: f32,
+        hitboxes: Vec<Hitbox>,
+    ) -> Self {
+        Self {
+            windup: Duration::from_secs_f32(windup),
+            active: Duration::from_secs_f32(active),
+            settle: Duration::from_secs_f32(settle),
+            momentum,
+            damage,
+            hitboxes,
+        }
+    }
+}
+
+/// Which way an attack was aimed when it started.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SlashDirection {
+    Forward,
+    Up,
+    // only in the air, bounces off whatever it hits
+    Down,
+}
 
 #[derive(Component)]
 pub struct DemoSlash {
     stage: Stage,
     stage_timer: Timer,
     has_hit: bool,
+    // the last attack is the finisher, after it the chain starts over
+    combo: Vec<SlashAttack>,
+    combo_index: usize,
+    up_attack: Option<SlashAttack>,
+    down_attack: Option<SlashAttack>,
+    slash_direction: SlashDirection,
+    // how long after an attack settles the next press still continues the chain
+    combo_timer: Timer,
+    // landing or leaving the ground mid attack interrupts the chain
+    started_grounded: bool,
 }
 
 pub enum Stage {
@@ -28,52 +103,167 @@ pub enum Stage {
 }
 
 impl DemoSlash {
-    pub fn new() -> Self {
+    pub fn new(
+        combo: Vec<SlashAttack>,
+        up_attack: Option<SlashAttack>,
+        down_attack: Option<SlashAttack>,
+        combo_timeout: f32,
+    ) -> Self {
+        // Starts expired so the first press opens the chain
+        let mut combo_timer = Timer::from_seconds(combo_timeout, TimerMode::Once);
+        combo_timer.tick(Duration::from_secs_f32(combo_timeout));
+
         Self {
             has_hit: false,
             stage: Stage::Dormant,
             stage_timer: Timer::from_seconds(0., TimerMode::Once),
+            combo,
+            combo_index: 0,
+            up_attack,
+            down_attack,
+            slash_direction: SlashDirection::Forward,
+            combo_timer,
+            started_grounded: false,
         }
     }
 
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
     pub fn set_stage(&mut self, next: Stage) {
+        let Some((windup, active, settle)) = self
+            .current_attack()
+            .map(|attack| (attack.windup, attack.active, attack.settle))
+        else {
+            self.stage = Stage::Dormant;
+            return;
+        };
+
         match next {
-            Stage::Windup | Stage::Settle => {
-                self.stage_timer.set_duration(Duration::from_secs_f32(0.1));
+            Stage::Windup => {
+                self.stage_timer.set_duration(windup);
             }
-            Stage::Active { .. } => {
+            Stage::Active => {
                 self.has_hit = false;
-                self.stage_timer.set_duration(Duration::from_secs_f32(0.3));
+                self.stage_timer.set_duration(active);
+            }
+            Stage::Settle => {
+                self.stage_timer.set_duration(settle);
+            }
+            Stage::Dormant => {
+                self.combo_timer.reset();
             }
-            _ => {}
         }
         self.stage = next;
         self.stage_timer.reset();
     }
+
+    pub fn current_attack(&self) -> Option<&SlashAttack> {
+        match self.slash_direction {
+            SlashDirection::Forward => self.combo.get(self.combo_index),
+            SlashDirection::Up => self.up_attack.as_ref(),
+            SlashDirection::Down => self.down_attack.as_ref(),
+        }
+    }
+
+    pub fn slash_direction(&self) -> SlashDirection {
+        self.slash_direction
+    }
+
+    pub fn is_finisher(&self) -> bool {
+        self.combo_index + 1 >= self.combo.len()
+    }
+
+    /// Picks the attack variant for the held `input` direction, directional attacks need a variant to exist.
+    fn aim(&self, input: InputDirection, grounded: &Grounded) -> SlashDirection {
+        match input {
+            InputDirection::Up | InputDirection::UpLeft | InputDirection::UpRight
+                if self.up_attack.is_some() =>
+            {
+                SlashDirection::Up
+            }
+            InputDirection::Down | InputDirection::DownLeft | InputDirection::DownRight
+                if self.down_attack.is_some() && !grounded.check() =>
+            {
+                SlashDirection::Down
+            }
+            _ => SlashDirection::Forward,
+        }
+    }
+
+    /// Stops the current attack and starts the chain over.
+    pub fn interrupt(&mut self) {
+        self.combo_index = 0;
+        self.stage = Stage::Dormant;
+        self.stage_timer.reset();
+        // Leave the combo timer expired so the next press opens the chain
+        let timeout = self.combo_timer.duration();
+        self.combo_timer.tick(timeout);
+    }
+
+    fn start_attack(
+        &mut self,
+        index: usize,
+        slash_direction: SlashDirection,
+        buffer: &mut InputBuffer,
+        grounded: &Grounded,
+    ) {
+        self.combo_index = index;
+        self.slash_direction = slash_direction;
+        self.started_grounded = grounded.check();
+        self.set_stage(Stage::Windup);
+
+        if grounded.check() {
+            buffer.block_all();
+        } else {
+            buffer.block(Blocker::non_directional());
+        }
+    }
 }
 
 pub fn demo_slash_player_behavior(
-    mut q_state: Query<
-        (
-            &mut LinearVelocity,
-            &mut InputBuffer,
-            &mut BehaviorInput<DemoSlash>,
-            &Grounded,
-            &FacingDirection,
-            &Transform,
-        ),
-        With<Player>,
-    >,
+    mut q_state: Query<(
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &mut DemoSlash,
+        &BehaviorInput<DemoSlash>,
+        Option<&mut Jump>,
+        Option<&mut Energy>,
+        &Grounded,
+        &FacingDirection,
+        &Transform,
+        &Faction,
+    )>,
     time: Res<Time>,
     mut shape_intersections: ShapeIntersections,
+    q_layers: Query<&CollisionLayers>,
+    mut struck: EventWriter<Struck>,
 ) {
-    for (mut vel, mut buffer, mut behavior_input, grounded, direction, transform) in
-        q_state.iter_mut()
+    for (
+        mut vel,
+        mut buffer,
+        mut behavior,
+        binding,
+        mut o_jump,
+        mut o_energy,
+        grounded,
+        direction,
+        transform,
+        faction,
+    ) in q_state.iter_mut()
     {
-        let (behavior, inputs) = behavior_input.get_mut();
+        let inputs = binding.get();
         let timer_finished = behavior.stage_timer.tick(time.delta()).finished();
+        let combo_timed_out = behavior.combo_timer.tick(time.delta()).finished();
 
-        match &behavior.stage {
+        match behavior.stage {
+            Stage::Windup | Stage::Active | Stage::Settle
+                if grounded.check() != behavior.started_grounded =>
+            {
+                buffer.clear_blocker();
+                behavior.interrupt();
+            }
             Stage::Dormant
                 if buffer
                     .query()
@@ -81,60 +271,99 @@ pub fn demo_slash_player_behavior(
                     .within_timeframe(Duration::from_millis(200))
                     .consume() =>
             {
-                behavior.set_stage(Stage::Windup);
+                let slash_direction = behavior.aim(buffer.this_frame().direction(), grounded);
+
+                // Only forward attacks chain, anything else starts the combo over
+                let next = if combo_timed_out
+                    || behavior.is_finisher()
+                    || behavior.slash_direction != SlashDirection::Forward
+                    || slash_direction != SlashDirection::Forward
+                {
+                    0
+                } else {
+                    behavior.combo_index + 1
+                };
+                behavior.start_attack(next, slash_direction, &mut buffer, grounded);
+
+                if grounded.check() {
+                    vel.x = 0.;
+                }
+            }
+            // Settling is the cancel window, pressing again goes straight into the next attack
+            Stage::Settle
+                if behavior.slash_direction == SlashDirection::Forward
+                    && !behavior.is_finisher()
+                    && buffer
+                        .query()
+                        .contains(inputs.just_pressed())
+                        .within_timeframe(Duration::from_millis(200))
+                        .consume() =>
+            {
+                let next = behavior.combo_index + 1;
+                behavior.start_attack(next, SlashDirection::Forward, &mut buffer, grounded);
 
                 if grounded.check() {
-                    buffer.block_all();
                     vel.x = 0.;
-                } else {
-                    buffer.block(Blocker::non_directional());
                 }
             }
             Stage::Windup if timer_finished => {
                 behavior.set_stage(Stage::Active);
+
+                if let Some(attack) = behavior.current_attack() {
+                    if grounded.check() {
+                        vel.x = attack.momentum * direction.get();
+                    }
+                }
             }
             Stage::Active if timer_finished => {
                 behavior.set_stage(Stage::Settle);
                 buffer.clear_blocker();
             }
             Stage::Active if !behavior.has_hit => {
-                let collider_size: f32 = 25.;
-                let slash_size_side = collider_size / 2.25;
-                for i in 0..2 {
-                    if let Some(other) = shape_intersections
+                let Some(attack) = behavior.current_attack() else {
+                    continue;
+                };
+                let damage = attack.damage;
+                let pogo = behavior.slash_direction == SlashDirection::Down;
+
+                // Downward attacks also bounce off hazards, so spikes can be crossed
+                let targets = if pogo {
+                    faction.targets() | HAZARD
+                } else {
+                    faction.targets()
+                };
+
+                let hit = attack.hitboxes.iter().find_map(|hitbox| {
+                    shape_intersections
                         .shape_intersections(
-                            &Collider::rectangle(slash_size_side, slash_size_side),
-                            transform.translation.xy()
-                                + Vec2::new(
-                                    collider_size * 2. * direction.get(),
-                                    collider_size
-                                        + slash_size_side
-                                        + 2. * (-collider_size - slash_size_side) * i as f32,
-                                ),
+                            &hitbox.collider(),
+                            hitbox.position(transform.translation.xy(), direction.get()),
                             0.,
-                            CollisionGroup::filter(ENEMY),
+                            CollisionGroup::filter(targets),
                         )
                         .first()
-                    {
-                        println!("Slashed: {other:?}",);
-                        behavior.has_hit = true;
-                        return;
-                    }
-                }
+                        .copied()
+                });
 
-                if let Some(other) = shape_intersections
-                    .shape_intersections(
-                        &Collider::rectangle(collider_size, collider_size),
-                        transform.translation.xy()
-                            + Vec2::new(collider_size * 2.5 * direction.get(), 0.),
-                        0.,
-                        CollisionGroup::filter(ENEMY),
-                    )
-                    .first()
-                {
-                    println!("Slashed: {other:?}",);
+                if let Some(other) = hit {
+                    println!("Slashed: {other:?} for {damage}");
+                    struck.send(Struck {
+                        entity: other,
+                        attack: AttackKind::Slash,
+                    });
                     behavior.has_hit = true;
-                };
+
+                    Energy::gain_from_hit(
+                        o_energy.as_deref_mut(),
+                        *faction,
+                        q_layers.get(other).ok(),
+                    );
+
+                    if let Some(jump) = o_jump.as_mut().filter(|_| pogo) {
+                        vel.y = jump.force();
+                        jump.reset_air_jump();
+                    }
+                }
             }
             Stage::Settle if timer_finished => {
                 behavior.set_stage(Stage::Dormant);
@@ -148,6 +377,9 @@ pub struct SlashingBehavior;
 
 impl Plugin for SlashingBehavior {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, demo_slash_player_behavior);
+        app.add_systems(
+            FixedUpdate,
+            demo_slash_player_behavior.in_set(BehaviorSet::Attacks),
+        );
     }
 }
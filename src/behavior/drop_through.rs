@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::input::buffer::InputBuffer;
+use crate::input::directions::InputDirection;
+use crate::state::grounded::Grounded;
+use crate::system_sets::BehaviorSet;
+use crate::world::components::OneWayPlatform;
+
+use super::crouch::Crouch;
+use super::BehaviorInput;
+
+/// Falls through the one-way platform underfoot when its input is pressed while holding crouch.
+///
+/// Crouch + jump is also a slide, so this runs first and takes the press whenever there is a
+/// platform to drop through. Everywhere else the press is left alone.
+#[derive(Component)]
+pub struct DropThrough {
+    stage: Stage,
+    stage_timer: Timer,
+    // how long platforms ignore us after dropping, long enough to fall clear of them
+    duration: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    Dropping,
+}
+
+impl DropThrough {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            stage_timer: Timer::from_seconds(0., TimerMode::Once),
+            duration,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        matches!(self.stage, Stage::Dropping)
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        if let Stage::Dropping = stage {
+            self.stage_timer
+                .set_duration(Duration::from_secs_f32(self.duration));
+            self.stage_timer.reset();
+        }
+        self.stage = stage;
+    }
+}
+
+pub fn drop_through_behavior_player(
+    mut q_player: Query<(
+        &Grounded,
+        &mut DropThrough,
+        &mut InputBuffer,
+        &BehaviorInput<DropThrough>,
+        &BehaviorInput<Crouch, InputDirection>,
+    )>,
+    q_platform: Query<(), With<OneWayPlatform>>,
+    time: Res<Time>,
+) {
+    for (grounded, mut state, mut buffer, binding, crouch_input) in q_player.iter_mut() {
+        let timer_finished = state.stage_timer.tick(time.delta()).finished();
+
+        let on_platform = grounded
+            .ground()
+            .is_some_and(|ground| q_platform.contains(ground.entity()));
+
+        match state.stage {
+            Stage::Dormant
+                if on_platform
+                    && buffer.is(crouch_input.get())
+                    && buffer
+                        .query()
+                        .contains(binding.get().just_pressed())
+                        .within_timeframe(Duration::from_millis(100))
+                        .consume() =>
+            {
+                state.set_stage(Stage::Dropping);
+            }
+            Stage::Dropping if timer_finished => {
+                state.set_stage(Stage::Dormant);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct DropThroughBehavior;
+
+impl Plugin for DropThroughBehavior {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            drop_through_behavior_player.in_set(BehaviorSet::Stance),
+        );
+    }
+}
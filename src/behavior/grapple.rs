@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+
+use crate::abilities::components::{Abilities, Ability};
+use crate::collision_groups::{CollisionGroup, ENVIRONMENT, GRAPPLE};
+use crate::input::blocker::Blocker;
+use crate::input::buffer::InputBuffer;
+use crate::input::directions::InputDirection;
+use crate::shape_intersections::ShapeIntersections;
+use crate::state::facing_direction::FacingDirection;
+use crate::system_sets::BehaviorSet;
+use crate::world::components::GrapplePoint;
+
+use super::jump::Jump;
+use super::ledge_grab::ledge_grab_player_behavior;
+use super::BehaviorInput;
+
+#[derive(Component)]
+pub struct Grapple {
+    stage: Stage,
+    range: f32,
+    min_length: f32,
+    reel_speed: f32,
+    swing_force: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    Attached { anchor: Entity, joint: Entity },
+}
+
+impl Grapple {
+    pub fn new(range: f32, min_length: f32, reel_speed: f32, swing_force: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            range,
+            min_length,
+            reel_speed,
+            swing_force,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        matches!(self.stage, Stage::Attached { .. })
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        self.stage = stage;
+    }
+
+    /// Spawns a static anchor at `point` and a rope joint tying it to `owner`, returning both.
+    pub fn spawn_rope(
+        commands: &mut Commands,
+        owner: Entity,
+        point: Vec2,
+        length: f32,
+    ) -> (Entity, Entity) {
+        let anchor = commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_translation(point.extend(0.))),
+                RigidBody::Static,
+                Name::new("GrappleAnchor"),
+            ))
+            .id();
+
+        let joint = commands
+            .spawn((
+                DistanceJoint::new(anchor, owner).with_rest_length(length),
+                Name::new("GrappleRope"),
+            ))
+            .id();
+
+        (anchor, joint)
+    }
+
+    /// Despawns the rope, leaving the owner's velocity untouched so momentum carries over.
+    pub fn release(&mut self, commands: &mut Commands) {
+        if let Stage::Attached { anchor, joint } = self.stage {
+            commands.entity(joint).despawn_recursive();
+            commands.entity(anchor).despawn_recursive();
+        }
+        self.set_stage(Stage::Dormant);
+    }
+}
+
+pub fn grapple_player_behavior(
+    mut commands: Commands,
+    mut q_player: Query<(
+        Entity,
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &mut Grapple,
+        &BehaviorInput<Grapple>,
+        Option<&BehaviorInput<Jump>>,
+        &FacingDirection,
+        &Transform,
+        Option<&Abilities>,
+    )>,
+    mut q_joint: Query<&mut DistanceJoint>,
+    q_grapple_point: Query<&Transform, With<GrapplePoint>>,
+    q_anchor: Query<&Transform, Without<Grapple>>,
+    time: Res<Time>,
+    mut shape_intersections: ShapeIntersections,
+) {
+    for (
+        entity,
+        mut vel,
+        mut buffer,
+        mut behavior,
+        binding,
+        o_jump_input,
+        direction,
+        transform,
+        o_abilities,
+    ) in q_player.iter_mut()
+    {
+        let position = transform.translation.xy();
+
+        // Directions are blocked for other behaviors while attached, so we read the raw frame
+        let frame = buffer.this_frame();
+
+        match behavior.stage {
+            Stage::Dormant
+                if Abilities::allows(o_abilities, Ability::Grapple)
+                    && buffer
+                        .query()
+                        .contains(binding.get().just_pressed())
+                        .within_timeframe(Duration::from_millis(200))
+                        .consume() =>
+            {
+                // Aim with the held direction, falling back to a diagonal up in front of us
+                let aim = match frame.direction() {
+                    InputDirection::Neutral => Vec2::new(direction.get(), 1.),
+                    held => held.as_vec2(),
+                };
+                let Ok(aim) = Dir2::new(aim) else {
+                    continue;
+                };
+
+                let Some(hit) = shape_intersections.spatial_query.cast_shape(
+                    &Collider::circle(5.),
+                    position,
+                    0.,
+                    aim,
+                    behavior.range,
+                    true,
+                    CollisionGroup::filter(ENVIRONMENT | GRAPPLE),
+                ) else {
+                    continue;
+                };
+
+                // Grapple points pull the hook to their center, anything else holds where it hit
+                let point = q_grapple_point
+                    .get(hit.entity)
+                    .map_or(position + *aim * hit.time_of_impact, |point| {
+                        point.translation.xy()
+                    });
+                let length = position.distance(point).max(behavior.min_length);
+
+                let (anchor, joint) = Grapple::spawn_rope(&mut commands, entity, point, length);
+                buffer.block(Blocker::directions());
+                behavior.set_stage(Stage::Attached { anchor, joint });
+            }
+            Stage::Attached { .. }
+                if frame.just_pressed(binding.get())
+                    || o_jump_input
+                        .is_some_and(|jump_input| frame.just_pressed(jump_input.get())) =>
+            {
+                // A jump press is left in the buffer so Jump or Kick can pick it up
+                buffer.clear_blocker();
+                behavior.release(&mut commands);
+            }
+            Stage::Attached { anchor, joint } => {
+                let (Ok(anchor_transform), Ok(mut rope)) =
+                    (q_anchor.get(anchor), q_joint.get_mut(joint))
+                else {
+                    buffer.clear_blocker();
+                    behavior.release(&mut commands);
+                    continue;
+                };
+
+                let anchor_position = anchor_transform.translation.xy();
+                let to_anchor = (anchor_position - position).normalize_or_zero();
+                let tangent = Vec2::new(to_anchor.y, -to_anchor.x);
+                let delta = time.delta_seconds();
+
+                vel.0 += tangent * frame.x() * behavior.swing_force * delta;
+
+                rope.rest_length = (rope.rest_length - frame.y() * behavior.reel_speed * delta)
+                    .clamp(behavior.min_length, behavior.range);
+
+                shape_intersections
+                    .gizmos
+                    .line_2d(position, anchor_position, css::ORANGE);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct GrappleBehavior;
+
+impl Plugin for GrappleBehavior {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            grapple_player_behavior
+                .in_set(BehaviorSet::Traversal)
+                .after(ledge_grab_player_behavior),
+        );
+    }
+}
@@ -3,47 +3,135 @@ use std::time::Duration;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::input::{buffer::InputBuffer, inputs::Inputs};
+use crate::abilities::components::{Abilities, Ability};
+use crate::input::buffer::InputBuffer;
 use crate::state::grounded::Grounded;
+use crate::state::submerged::Submerged;
+use crate::system_sets::BehaviorSet;
 
 use super::crouch::Crouch;
 use super::slide::Slide;
+use super::swim::Swim;
+use super::BehaviorInput;
+
+/// Tuning for the shape of a jump, all heights and speeds are in world units.
+#[derive(Clone, Copy)]
+pub struct JumpArc {
+    /// Height reached by a full jump.
+    pub apex_height: f32,
+    /// Seconds it takes a full jump to reach `apex_height`.
+    pub time_to_apex: f32,
+    /// Multiplier on the rising gravity used while falling.
+    pub fall_multiplier: f32,
+    /// Seconds spent at reduced gravity after passing the apex.
+    pub apex_hang: f32,
+    /// Multiplier on the rising gravity used while hanging at the apex.
+    pub apex_gravity_factor: f32,
+    /// Fraction of the upward velocity kept when jump is released early.
+    pub release_cut: f32,
+    /// Maximum downward speed.
+    pub terminal_velocity: f32,
+}
+
+impl JumpArc {
+    /// Initial upward velocity needed to reach the apex in time.
+    pub fn initial_velocity(&self) -> f32 {
+        2. * self.apex_height / self.time_to_apex
+    }
+
+    /// Gravity strength needed to reach the apex in time.
+    pub fn gravity(&self) -> f32 {
+        2. * self.apex_height / (self.time_to_apex * self.time_to_apex)
+    }
+}
 
 #[derive(Component)]
 pub struct Jump {
-    has_air_jumped: bool,
-    force: f32,
+    air_jumps: u32,
+    max_air_jumps: u32,
+    // cleared once a grounded jump is spent, so coyote time can't grant a second one
+    coyote_available: bool,
+    // set by other behaviors that take over the character's gravity, like kicks or hanging
+    gravity_suspended: bool,
+    arc: JumpArc,
+    // how long after leaving the ground a jump still counts as grounded
+    coyote_time: Duration,
+    // how early a press is remembered before it can be acted on, e.g. right before landing
+    buffer_window: Duration,
     stage: Stage,
+    stage_timer: Timer,
 }
 
 pub enum Stage {
     Dormant,
     Active,
+    Apex,
 }
 
 impl Jump {
     pub fn set_stage(&mut self, stage: Stage) {
+        if let Stage::Apex = stage {
+            self.stage_timer
+                .set_duration(Duration::from_secs_f32(self.arc.apex_hang));
+        }
         self.stage = stage;
+        self.stage_timer.reset();
     }
 
-    pub fn new(jump_force: f32) -> Self {
+    pub fn new(arc: JumpArc, max_air_jumps: u32, coyote_time: f32, buffer_window: f32) -> Self {
         Self {
-            has_air_jumped: false,
-            force: jump_force,
+            air_jumps: 0,
+            max_air_jumps,
+            coyote_available: true,
+            gravity_suspended: false,
+            arc,
+            coyote_time: Duration::from_secs_f32(coyote_time),
+            buffer_window: Duration::from_secs_f32(buffer_window),
             stage: Stage::Dormant,
+            stage_timer: Timer::from_seconds(0., TimerMode::Once),
         }
     }
 
     pub fn has_air_jumped(&self) -> bool {
-        self.has_air_jumped
+        self.air_jumps > 0
     }
 
     pub fn force(&self) -> f32 {
-        self.force
+        self.arc.initial_velocity()
     }
 
     pub fn reset_air_jump(&mut self) {
-        self.has_air_jumped = false;
+        self.air_jumps = 0;
+    }
+
+    /// Hands control of the character's gravity to another behavior until `resume_gravity` is called.
+    pub fn suspend_gravity(&mut self) {
+        self.gravity_suspended = true;
+    }
+
+    pub fn resume_gravity(&mut self) {
+        self.gravity_suspended = false;
+    }
+
+    /// Checks if a jump right now counts as grounded, either on the ground or within the coyote window.
+    pub fn can_ground_jump(&self, grounded: &Grounded) -> bool {
+        grounded.check()
+            || (self.coyote_available && grounded.time_since_left_ground() <= self.coyote_time)
+    }
+
+    /// Returns the gravity scale for the current point in the arc, relative to the world `gravity`.
+    pub fn gravity_scale(&self, grounded: &Grounded, vel_y: f32, gravity: f32) -> f32 {
+        if self.gravity_suspended {
+            return 0.;
+        }
+
+        let rising = self.arc.gravity() / gravity;
+
+        match self.stage {
+            Stage::Apex => rising * self.arc.apex_gravity_factor,
+            _ if vel_y < 0. && !grounded.check() => rising * self.arc.fall_multiplier,
+            _ => rising,
+        }
     }
 }
 
@@ -51,35 +139,94 @@ pub fn jumping_behavior_player(
     mut q_state: Query<(
         Option<&Crouch>,
         Option<&Slide>,
+        Option<&Swim>,
+        Option<&Submerged>,
+        Option<&Abilities>,
         &Grounded,
         &mut LinearVelocity,
+        &mut GravityScale,
         &mut Jump,
+        &BehaviorInput<Jump>,
         &mut InputBuffer,
     )>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
 ) {
-    for (o_crouch, o_slide, grounded, mut vel, mut state, mut input_buffer) in q_state.iter_mut() {
-        if o_crouch.is_some_and(Crouch::check) && o_slide.is_some() {
-            return;
+    for (
+        o_crouch,
+        o_slide,
+        o_swim,
+        o_submerged,
+        o_abilities,
+        grounded,
+        mut vel,
+        mut gravity_scale,
+        mut state,
+        binding,
+        mut input_buffer,
+    ) in q_state.iter_mut()
+    {
+        let timer_finished = state.stage_timer.tick(time.delta()).finished();
+
+        gravity_scale.0 = state.gravity_scale(grounded, vel.y, gravity.0.length());
+        vel.y = vel.y.max(-state.arc.terminal_velocity);
+
+        // Crouching hands the jump press over to sliding, if we're able to slide
+        if o_crouch.is_some_and(Crouch::check)
+            && o_slide.is_some()
+            && Abilities::allows(o_abilities, Ability::Slide)
+        {
+            continue;
         }
 
+        // Contacts can linger for a frame after take-off, so only refresh once we stop moving off the ground
+        if grounded.footing(vel.0).is_some() {
+            state.coyote_available = true;
+            state.reset_air_jump();
+        }
+
+        // Underwater the only jump is out of the water, and it's a weaker one
+        let swimming = o_swim.is_some_and(Swim::check);
+        let surfacing = o_swim
+            .zip(o_submerged)
+            .is_some_and(|(swim, submerged)| swim.at_surface(submerged));
+        let force_factor = match o_swim {
+            Some(swim) if surfacing => swim.surface_jump_factor(),
+            _ => 1.,
+        };
+
+        let ground_jump = surfacing || state.can_ground_jump(grounded);
+        let air_jump = state.air_jumps < state.max_air_jumps
+            && Abilities::allows(o_abilities, Ability::DoubleJump);
+        let buffer_window = state.buffer_window;
+
         match state.stage {
-            Stage::Dormant
-                if (!state.has_air_jumped || grounded.check())
+            Stage::Dormant | Stage::Apex
+                if (!swimming || surfacing)
+                    && (air_jump || ground_jump)
                     && input_buffer
                         .query()
-                        .contains(Inputs::Jump.just_pressed())
-                        .within_timeframe(Duration::from_millis(200))
+                        .contains(binding.get().just_pressed())
+                        .within_timeframe(buffer_window)
                         .consume() =>
             {
-                state.has_air_jumped = !grounded.check();
+                if !ground_jump {
+                    state.air_jumps += 1;
+                }
+                state.coyote_available = false;
 
                 state.set_stage(Stage::Active);
-                vel.y = state.force;
-                return;
+                vel.y = state.force() * force_factor;
+            }
+            Stage::Active if input_buffer.is(binding.get().released()) => {
+                state.set_stage(Stage::Dormant);
+                vel.y *= state.arc.release_cut;
+            }
+            Stage::Active if vel.y <= 0. => {
+                state.set_stage(Stage::Apex);
             }
-            Stage::Active if input_buffer.is(Inputs::Jump.released()) || vel.y < 0. => {
+            Stage::Apex if timer_finished || grounded.check() => {
                 state.set_stage(Stage::Dormant);
-                vel.y /= 2.;
             }
             _ => {}
         }
@@ -90,6 +237,9 @@ pub struct JumpBehavior;
 
 impl Plugin for JumpBehavior {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, jumping_behavior_player);
+        app.add_systems(
+            FixedUpdate,
+            jumping_behavior_player.in_set(BehaviorSet::Airborne),
+        );
     }
 }
@@ -3,24 +3,29 @@ use std::time::Duration;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
+use crate::abilities::components::{Abilities, Ability};
 use crate::input::buffer::InputBuffer;
 use crate::input::directions::InputDirection;
-use crate::input::inputs::Inputs;
 use crate::shape_intersections::ShapeIntersections;
 
+use crate::state::energy::Energy;
 use crate::state::facing_direction::FacingDirection;
 use crate::state::grounded::Grounded;
 use crate::{
+    characters::components::{Body, Faction},
     collision_groups::*,
-    player::components::{Body, Player},
+    system_sets::BehaviorSet,
 };
 
+use super::crouch::Crouch;
 use super::jump::{self, jumping_behavior_player, Jump};
+use super::{AttackKind, BehaviorInput, Struck};
 
 #[derive(Component)]
 pub struct Kick {
     stage: Stage,
     kick_speed: f32,
+    energy_cost: f32,
 }
 
 pub enum Stage {
@@ -29,34 +34,42 @@ pub enum Stage {
 }
 
 impl Kick {
-    pub fn new(kick_speed: f32) -> Self {
+    pub fn new(kick_speed: f32, energy_cost: f32) -> Self {
         Self {
             stage: Stage::Dormant,
             kick_speed,
+            energy_cost,
         }
     }
 
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
     pub fn set_stage(&mut self, stage: Stage) {
         self.stage = stage;
     }
 }
 
 pub fn kicking_behavior_player(
-    mut q_state: Query<
-        (
-            &mut LinearVelocity,
-            &mut InputBuffer,
-            &mut Jump,
-            &mut Kick,
-            &Body,
-            &Grounded,
-            &Transform,
-            &mut GravityScale,
-            &FacingDirection,
-        ),
-        With<Player>,
-    >,
+    mut q_state: Query<(
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &mut Jump,
+        &mut Kick,
+        &Body,
+        &Grounded,
+        &Transform,
+        &FacingDirection,
+        &Faction,
+        &BehaviorInput<Kick>,
+        &BehaviorInput<Crouch, InputDirection>,
+        Option<&Abilities>,
+        Option<&mut Energy>,
+    )>,
     mut shape_intersections: ShapeIntersections,
+    q_layers: Query<&CollisionLayers>,
+    mut struck: EventWriter<Struck>,
 ) {
     for (
         mut vel,
@@ -66,28 +79,39 @@ pub fn kicking_behavior_player(
         body,
         grounded,
         transform,
-        mut gravity,
         facing_direction,
+        faction,
+        binding,
+        crouch_input,
+        o_abilities,
+        mut o_energy,
     ) in q_state.iter_mut()
     {
         match state.stage {
-            Stage::Dormant if jump.has_air_jumped() => {
+            Stage::Dormant
+                if jump.has_air_jumped()
+                    && Abilities::allows(o_abilities, Ability::Kick)
+                    && Energy::affords(o_energy.as_deref(), state.energy_cost) =>
+            {
+                // Kicks aim along the crouch direction or either diagonal next to it
                 let x = match buffer
                     .query()
                     .within_timeframe(Duration::from_millis(200))
-                    .contains_any(vec![Inputs::Jump.just_pressed()])
-                    .contains_any(
-                        InputDirection::DownRight.roll_clockwise(InputDirection::DownLeft),
-                    )
+                    .contains_any(vec![binding.get().just_pressed()])
+                    .contains_any(crouch_input.get().around())
                     .consume_recent()
                 {
                     Some(frame) => frame.x(),
-                    None => return,
+                    None => continue,
                 };
 
+                if let Some(energy) = o_energy.as_mut() {
+                    energy.spend(state.energy_cost);
+                }
+
                 buffer.block_all();
                 state.set_stage(Stage::Active);
-                gravity.0 = 0.;
+                jump.suspend_gravity();
 
                 if vel.x.signum() * x.signum() < -0.2 || vel.x.abs() < state.kick_speed {
                     vel.x = state.kick_speed * x.abs().ceil().copysign(x) * 0.9;
@@ -98,7 +122,7 @@ pub fn kicking_behavior_player(
             }
             Stage::Active if grounded.check() => {
                 state.stage = Stage::Dormant;
-                gravity.0 = 1.;
+                jump.resume_gravity();
                 buffer.clear_blocker();
             }
             Stage::Active => {
@@ -111,14 +135,24 @@ pub fn kicking_behavior_player(
                                 -body.height / 4.,
                             ),
                         0.,
-                        CollisionGroup::filter(ENEMY),
+                        CollisionGroup::targets(*faction),
                     )
                     .first()
                 {
                     println!("Kicked: {other:?}");
+                    struck.send(Struck {
+                        entity: *other,
+                        attack: AttackKind::Kick,
+                    });
+                    Energy::gain_from_hit(
+                        o_energy.as_deref_mut(),
+                        *faction,
+                        q_layers.get(*other).ok(),
+                    );
+
                     state.set_stage(Stage::Dormant);
                     buffer.clear_blocker();
-                    gravity.0 = 1.;
+                    jump.resume_gravity();
 
                     jump.set_stage(jump::Stage::Active);
                     vel.y = jump.force();
@@ -135,8 +169,10 @@ pub struct KickingBehavior;
 impl Plugin for KickingBehavior {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
-            kicking_behavior_player.before(jumping_behavior_player),
+            FixedUpdate,
+            kicking_behavior_player
+                .in_set(BehaviorSet::Airborne)
+                .before(jumping_behavior_player),
         );
     }
 }
@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::abilities::components::{Abilities, Ability};
+use crate::characters::components::Body;
+use crate::collision_groups::{CollisionGroup, ENVIRONMENT};
+use crate::input::buffer::InputBuffer;
+use crate::input::directions::InputDirection;
+use crate::shape_intersections::ShapeIntersections;
+use crate::state::facing_direction::FacingDirection;
+use crate::state::grounded::Grounded;
+use crate::system_sets::BehaviorSet;
+
+use super::climb::climbing_behavior_player;
+use super::crouch::Crouch;
+use super::jump::{self, Jump};
+use super::BehaviorInput;
+
+#[derive(Component)]
+pub struct LedgeGrab {
+    stage: Stage,
+    stage_timer: Timer,
+    // how far in front of the body a ledge can be grabbed from
+    reach: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    Hanging,
+    Climbing { target: Vec2 },
+    // short window after letting go where the same ledge can't be re-grabbed
+    Released,
+}
+
+impl LedgeGrab {
+    pub fn new(reach: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            stage_timer: Timer::from_seconds(0., TimerMode::Once),
+            reach,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        matches!(self.stage, Stage::Hanging | Stage::Climbing { .. })
+    }
+
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        match stage {
+            Stage::Climbing { .. } => {
+                self.stage_timer.set_duration(Duration::from_secs_f32(0.2));
+            }
+            Stage::Released => {
+                self.stage_timer.set_duration(Duration::from_secs_f32(0.3));
+            }
+            _ => {}
+        }
+        self.stage = stage;
+        self.stage_timer.reset();
+    }
+
+    /// Looks for a ledge corner in front of `body`, returning the corner position if one is in reach.
+    ///
+    /// A corner is a wall at the top of the body with free space directly above it.
+    pub fn find_ledge(
+        &self,
+        shape_intersections: &mut ShapeIntersections,
+        body: &Body,
+        position: Vec2,
+        direction: f32,
+    ) -> Option<Vec2> {
+        let probe_x = position.x + (body.width / 2. + self.reach / 2.) * direction;
+        let top = position.y + body.height / 2.;
+
+        let wall = !shape_intersections
+            .shape_intersections(
+                &Collider::rectangle(self.reach, self.reach),
+                Vec2::new(probe_x, top - self.reach / 2.),
+                0.,
+                CollisionGroup::filter(ENVIRONMENT),
+            )
+            .is_empty();
+
+        let free_above = shape_intersections
+            .shape_intersections(
+                &Collider::rectangle(self.reach, self.reach),
+                Vec2::new(probe_x, top + self.reach / 2.),
+                0.,
+                CollisionGroup::filter(ENVIRONMENT),
+            )
+            .is_empty();
+
+        if !wall || !free_above {
+            return None;
+        }
+
+        let ledge_top = shape_intersections.spatial_query.cast_ray(
+            Vec2::new(probe_x, top + self.reach),
+            Dir2::NEG_Y,
+            self.reach * 2.,
+            true,
+            CollisionGroup::filter(ENVIRONMENT),
+        )?;
+        let ledge_y = top + self.reach - ledge_top.time_of_impact;
+
+        let wall_face = shape_intersections.spatial_query.cast_ray(
+            Vec2::new(position.x, ledge_y - 1.),
+            if direction < 0. { Dir2::NEG_X } else { Dir2::X },
+            body.width / 2. + self.reach,
+            true,
+            CollisionGroup::filter(ENVIRONMENT),
+        )?;
+        let ledge_x = position.x + wall_face.time_of_impact * direction;
+
+        Some(Vec2::new(ledge_x, ledge_y))
+    }
+}
+
+pub fn ledge_grab_player_behavior(
+    mut q_player: Query<(
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &mut Jump,
+        &BehaviorInput<Jump>,
+        &mut LedgeGrab,
+        &BehaviorInput<LedgeGrab, InputDirection>,
+        &BehaviorInput<Crouch, InputDirection>,
+        Option<&Crouch>,
+        &Body,
+        &Grounded,
+        &FacingDirection,
+        Option<&Abilities>,
+    )>,
+    time: Res<Time>,
+    mut shape_intersections: ShapeIntersections,
+) {
+    for (
+        mut transform,
+        mut vel,
+        mut buffer,
+        mut jump,
+        jump_input,
+        mut state,
+        binding,
+        crouch_input,
+        o_crouch,
+        body,
+        grounded,
+        direction,
+        o_abilities,
+    ) in q_player.iter_mut()
+    {
+        let timer_finished = state.stage_timer.tick(time.delta()).finished();
+
+        // While hanging all input is blocked for other behaviors, so we read the raw frame
+        let frame = buffer.this_frame();
+
+        match state.stage {
+            Stage::Dormant
+                if !grounded.check()
+                    && vel.y <= 0.
+                    && Abilities::allows(o_abilities, Ability::LedgeGrab)
+                    && !o_crouch.is_some_and(Crouch::check)
+                    && !buffer.is(crouch_input.get()) =>
+            {
+                let Some(ledge) = state.find_ledge(
+                    &mut shape_intersections,
+                    body,
+                    transform.translation.xy(),
+                    direction.get(),
+                ) else {
+                    continue;
+                };
+
+                transform.translation.x = ledge.x - body.width / 2. * direction.get();
+                transform.translation.y = ledge.y - body.height / 2. + body.height / 8.;
+                *vel = LinearVelocity::ZERO;
+                jump.suspend_gravity();
+
+                jump.reset_air_jump();
+                buffer.block_all();
+                state.set_stage(Stage::Hanging);
+            }
+            Stage::Hanging if frame.just_pressed(jump_input.get()) => {
+                buffer.clear_blocker();
+                jump.resume_gravity();
+                state.set_stage(Stage::Released);
+
+                jump.set_stage(jump::Stage::Active);
+                vel.y = jump.force();
+            }
+            Stage::Hanging if frame.check_direction(crouch_input.get()) => {
+                buffer.clear_blocker();
+                jump.resume_gravity();
+                state.set_stage(Stage::Released);
+            }
+            Stage::Hanging if frame.check_direction(binding.get()) => {
+                // Stand on top of the ledge, just past the corner
+                let target = Vec2::new(
+                    transform.translation.x + body.width * direction.get(),
+                    transform.translation.y + body.height - body.height / 8. + 1.,
+                );
+
+                // The standing collider has to fit, checked one half at a time
+                if Crouch::has_standing_clearance(&mut shape_intersections, body, target)
+                    && Crouch::has_standing_clearance(
+                        &mut shape_intersections,
+                        body,
+                        target - Vec2::new(0., body.height / 2.),
+                    )
+                {
+                    state.set_stage(Stage::Climbing { target });
+                }
+            }
+            Stage::Hanging => {
+                *vel = LinearVelocity::ZERO;
+            }
+            Stage::Climbing { target } if timer_finished => {
+                transform.translation = target.extend(transform.translation.z);
+                *vel = LinearVelocity::ZERO;
+                jump.resume_gravity();
+                buffer.clear_blocker();
+                state.set_stage(Stage::Dormant);
+            }
+            Stage::Climbing { .. } => {
+                *vel = LinearVelocity::ZERO;
+            }
+            Stage::Released if timer_finished => {
+                state.set_stage(Stage::Dormant);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct LedgeGrabBehavior;
+
+impl Plugin for LedgeGrabBehavior {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            ledge_grab_player_behavior
+                .in_set(BehaviorSet::Traversal)
+                .after(climbing_behavior_player),
+        );
+    }
+}
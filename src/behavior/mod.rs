@@ -1,26 +1,36 @@
+use std::marker::PhantomData;
+
 use bevy::app::Plugin;
 use bevy::prelude::*;
 
 use crate::input::inputs::Inputs;
 
 use self::{
-    crouch::CrouchBehavior, demo_slash::SlashingBehavior, jump::JumpBehavior,
-    kick::KickingBehavior, shot::ShotBehavior, slide::SlidingBehavior, walk::WalkBehavior,
+    climb::ClimbBehavior, crawl::CrawlBehavior, crouch::CrouchBehavior,
+    demo_slash::SlashingBehavior, drop_through::DropThroughBehavior, grapple::GrappleBehavior,
+    jump::JumpBehavior, kick::KickingBehavior, ledge_grab::LedgeGrabBehavior, shot::ShotBehavior,
+    slide::SlidingBehavior, swim::SwimBehavior, walk::WalkBehavior,
 };
 
+pub mod climb;
+pub mod crawl;
 pub mod crouch;
 pub mod demo_slash;
+pub mod drop_through;
+pub mod grapple;
 pub mod jump;
 pub mod kick;
+pub mod ledge_grab;
 pub mod shot;
 pub mod slide;
+pub mod swim;
 pub mod walk;
 
 pub struct BehaviorPlugin;
 
 impl Plugin for BehaviorPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_plugins((
+        app.add_event::<Struck>().add_plugins((
             KickingBehavior,
             SlidingBehavior,
             JumpBehavior,
@@ -28,22 +38,57 @@ impl Plugin for BehaviorPlugin {
             CrouchBehavior,
             ShotBehavior,
             WalkBehavior,
+            LedgeGrabBehavior,
+            ClimbBehavior,
+            SwimBehavior,
+            GrappleBehavior,
+            CrawlBehavior,
+            DropThroughBehavior,
         ));
     }
 }
 
+/// The attacks that can land a hit, for anything that only reacts to some of them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttackKind {
+    Slash,
+    Slide,
+    Kick,
+    Shot,
+    ChargedShot,
+}
+
+/// Sent whenever an attack hits something, be it a hurtbox or a breakable.
+#[derive(Event)]
+pub struct Struck {
+    pub entity: Entity,
+    pub attack: AttackKind,
+}
+
+/// The input that triggers behavior `T`, kept apart from the behavior so it can be rebound per character.
+///
+/// Most behaviors are triggered by an action, those triggered by a held direction use
+/// `BehaviorInput<T, InputDirection>` instead.
 #[derive(Component)]
-pub struct BehaviorInput<T: Component> {
-    pub input: Inputs,
-    pub behavior: T,
+pub struct BehaviorInput<T: Component, I = Inputs> {
+    input: I,
+    behavior: PhantomData<T>,
 }
 
-impl<T: Component> BehaviorInput<T> {
-    pub fn new(input: Inputs, behavior: T) -> Self {
-        Self { input, behavior }
+impl<T: Component, I: Copy> BehaviorInput<T, I> {
+    pub fn new(input: I) -> Self {
+        Self {
+            input,
+            behavior: PhantomData,
+        }
+    }
+
+    pub fn get(&self) -> I {
+        self.input
     }
 
-    pub fn get_mut(&mut self) -> (&mut T, Inputs) {
-        (&mut self.behavior, self.input)
+    /// Rebinds the behavior, taking effect from the next frame it checks its input.
+    pub fn set(&mut self, input: I) {
+        self.input = input;
     }
 }
@@ -1,27 +1,66 @@
 use std::time::Duration;
 
 use avian2d::prelude::*;
+use bevy::color::palettes::css;
 use bevy::prelude::*;
 
 use crate::{
+    abilities::components::{Abilities, Ability},
+    characters::components::Faction,
     collision_groups::*,
     input::{blocker::Blocker, buffer::InputBuffer},
-    player::components::Player,
     shape_intersections::ShapeIntersections,
-    state::{facing_direction::FacingDirection, grounded::Grounded},
+    state::{
+        energy::Energy, facing_direction::FacingDirection, grounded::Grounded, submerged::Submerged,
+    },
+    system_sets::{BehaviorSet, GameplaySet},
+    world::{components::Water, resources::RoomBounds},
 };
 
-use super::BehaviorInput;
+use super::demo_slash::demo_slash_player_behavior;
+use super::{AttackKind, BehaviorInput, Struck};
+
+/// Describes how a kind of projectile flies and what it does on impact.
+#[derive(Asset, TypePath, Clone)]
+pub struct ProjectileType {
+    pub name: String,
+    pub speed: f32,
+    /// Scale of the world gravity applied to the projectile, 0 flies straight.
+    pub gravity: f32,
+    /// Seconds before the projectile despawns on its own.
+    pub lifetime: f32,
+    /// How many targets it passes through before despawning on the next one.
+    pub pierce: u32,
+    /// How many times it bounces off the environment before despawning on the next hit.
+    pub bounces: u32,
+    pub size: f32,
+    /// Energy spent firing it, the shot is refused if the shooter can't cover it.
+    pub energy_cost: f32,
+}
 
 #[derive(Component)]
 pub struct Shot {
     stage: Stage,
     stage_timer: Timer,
+    bullet: Handle<ProjectileType>,
+    charged: Handle<ProjectileType>,
+    // how long the input has to be held for a charged shot on release
+    charge_timer: Timer,
 }
 
 #[derive(Component)]
 pub struct Projectile {
-    direction: f32,
+    velocity: Vec2,
+    gravity: f32,
+    size: f32,
+    lifetime: Timer,
+    // layers the shooter's attacks hit
+    targets: LayerMask,
+    attack: AttackKind,
+    pierces_left: u32,
+    bounces_left: u32,
+    // targets already hit, so piercing shots only hit each once
+    hits: Vec<Entity>,
 }
 
 pub enum Stage {
@@ -30,10 +69,17 @@ pub enum Stage {
 }
 
 impl Shot {
-    pub fn new() -> Self {
+    pub fn new(
+        bullet: Handle<ProjectileType>,
+        charged: Handle<ProjectileType>,
+        charge_time: f32,
+    ) -> Self {
         Self {
             stage: Stage::Dormant,
             stage_timer: Timer::from_seconds(0.5, TimerMode::Once),
+            bullet,
+            charged,
+            charge_timer: Timer::from_seconds(charge_time, TimerMode::Once),
         }
     }
 
@@ -42,63 +88,191 @@ impl Shot {
         self.stage_timer.reset();
     }
 
-    pub fn spawn_projectile(commands: &mut Commands, origin: Vec3, direction: f32) {
+    pub fn spawn_projectile(
+        commands: &mut Commands,
+        origin: Vec3,
+        direction: f32,
+        kind: &ProjectileType,
+        targets: LayerMask,
+        attack: AttackKind,
+    ) {
         commands.spawn((
             SpatialBundle::from_transform(Transform::from_translation(origin)),
-            Projectile { direction },
-            Name::new("Bullet"),
+            Projectile {
+                velocity: Vec2::new(kind.speed * direction, 0.),
+                gravity: kind.gravity,
+                size: kind.size,
+                lifetime: Timer::from_seconds(kind.lifetime, TimerMode::Once),
+                targets,
+                attack,
+                pierces_left: kind.pierce,
+                bounces_left: kind.bounces,
+                hits: Vec::new(),
+            },
+            // Projectiles move themselves, the collider is only there for trigger volumes to find
+            CollisionGroup::PROJECTILE,
+            Sensor,
+            Collider::rectangle(kind.size, kind.size),
+            Submerged::new(),
+            Name::new(kind.name.clone()),
         ));
     }
 }
 
+/// Moves projectiles with swept shape casts, so fast projectiles can't tunnel through thin walls.
 pub fn projectile_behavior(
     mut commands: Commands,
-    mut q_bullet: Query<(Entity, &mut Transform, &Projectile)>,
+    mut q_bullet: Query<(Entity, &mut Transform, &mut Projectile, &Submerged)>,
+    q_layers: Query<&CollisionLayers>,
+    room: Res<RoomBounds>,
+    gravity: Res<Gravity>,
     time: Res<Time>,
     mut shape_intersections: ShapeIntersections,
+    mut struck: EventWriter<Struck>,
 ) {
-    for (collider, mut transform, projectile) in q_bullet.iter_mut() {
-        transform.translation.x += 500. * time.delta_seconds() * projectile.direction;
-
-        // I use a rectangle collider because circle colliders dont render in debug for some reason
-        if let Some(other) = shape_intersections
-            .shape_intersections(
-                &Collider::rectangle(15., 15.),
-                transform.translation.xy(),
+    for (entity, mut transform, mut projectile, submerged) in q_bullet.iter_mut() {
+        let mut position = transform.translation.xy();
+
+        let left_room = !room.contains(position);
+        if projectile.lifetime.tick(time.delta()).finished() || left_room {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let delta = time.delta_seconds();
+        let gravity_scale = projectile.gravity;
+        projectile.velocity += gravity.0 * gravity_scale * delta;
+
+        // Swept as a square, the same square that's drawn for it below
+        let shape = Collider::rectangle(projectile.size, projectile.size);
+        let speed_factor = submerged.water().map_or(1., Water::projectile_speed);
+        let mut remaining = projectile.velocity.length() * speed_factor * delta;
+        let mut despawned = false;
+
+        // Every impact either ends the loop or spends a pierce or bounce, so this always finishes
+        while remaining > 0. {
+            let Ok(direction) = Dir2::new(projectile.velocity) else {
+                break;
+            };
+
+            let Some(hit) = shape_intersections.spatial_query.cast_shape(
+                &shape,
+                position,
                 0.,
-                CollisionGroup::filter(ENEMY | ENVIRONMENT),
-            )
-            .first()
-        {
-            // Since we dont have "hp" yet, we just despawn the bullet
-            println!("Shot hit: {other:?}");
-            commands.entity(collider).despawn_recursive();
+                direction,
+                remaining,
+                true,
+                CollisionGroup::projectile(projectile.targets)
+                    .with_excluded_entities(projectile.hits.iter().copied()),
+            ) else {
+                position += *direction * remaining;
+                break;
+            };
+
+            position += *direction * hit.time_of_impact;
+            remaining -= hit.time_of_impact;
+
+            let memberships = q_layers
+                .get(hit.entity)
+                .map_or(0, |layers| layers.memberships.0);
+
+            if memberships & projectile.targets.0 != 0 {
+                struck.send(Struck {
+                    entity: hit.entity,
+                    attack: projectile.attack,
+                });
+            }
+
+            // Breakables are targets but still solid, so we bounce off them like any other wall
+            let hit_target =
+                memberships & projectile.targets.0 != 0 && memberships & ENVIRONMENT.0 == 0;
+
+            if hit_target {
+                println!("Shot hit: {:?}", hit.entity);
+                projectile.hits.push(hit.entity);
+
+                if projectile.pierces_left == 0 {
+                    despawned = true;
+                    break;
+                }
+                projectile.pierces_left -= 1;
+            } else {
+                if projectile.bounces_left == 0 {
+                    despawned = true;
+                    break;
+                }
+                projectile.bounces_left -= 1;
+
+                let normal = hit.normal1;
+                projectile.velocity -= 2. * projectile.velocity.dot(normal) * normal;
+            }
         }
+
+        if despawned {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation = position.extend(transform.translation.z);
+        shape_intersections.gizmos.primitive_2d(
+            &Rectangle::new(projectile.size, projectile.size),
+            position,
+            0.,
+            css::YELLOW,
+        );
     }
 }
 
 pub fn shot_player_behavior(
     mut commands: Commands,
     time: Res<Time>,
-    mut q_player: Query<
-        (
-            &Transform,
-            &mut InputBuffer,
-            &mut LinearVelocity,
-            &FacingDirection,
-            &mut BehaviorInput<Shot>,
-            &Grounded,
-        ),
-        With<Player>,
-    >,
+    projectile_types: Res<Assets<ProjectileType>>,
+    mut q_player: Query<(
+        &Transform,
+        &mut InputBuffer,
+        &mut LinearVelocity,
+        &FacingDirection,
+        &Faction,
+        &mut Shot,
+        &BehaviorInput<Shot>,
+        &Grounded,
+        Option<&Abilities>,
+        Option<&mut Energy>,
+    )>,
 ) {
-    for (transform, mut buffer, mut velocity, direction, mut behavior_input, grounded) in
-        q_player.iter_mut()
+    for (
+        transform,
+        mut buffer,
+        mut velocity,
+        direction,
+        faction,
+        mut behavior,
+        binding,
+        grounded,
+        o_abilities,
+        mut o_energy,
+    ) in q_player.iter_mut()
     {
-        let (behavior, inputs) = behavior_input.get_mut();
+        let inputs = binding.get();
         let timer_finished = behavior.stage_timer.tick(time.delta()).finished();
 
-        match &behavior.stage {
+        // Charging only cares about the button itself, so a stall doesn't interrupt it
+        let frame = buffer.this_frame();
+        let charged = behavior.charge_timer.finished();
+        if frame.pressed(inputs) {
+            behavior.charge_timer.tick(time.delta());
+        } else if !frame.just_released(inputs) {
+            behavior.charge_timer.reset();
+        }
+
+        if !Abilities::allows(o_abilities, Ability::Shot) {
+            continue;
+        }
+
+        let (kind, attack) = match behavior.stage {
+            _ if charged && frame.just_released(inputs) => {
+                (behavior.charged.clone(), AttackKind::ChargedShot)
+            }
             Stage::Dormant
                 if buffer
                     .query()
@@ -106,21 +280,43 @@ pub fn shot_player_behavior(
                     .within_timeframe(Duration::from_millis(200))
                     .consume() =>
             {
-                behavior.set_stage(Stage::Stall);
-                Shot::spawn_projectile(&mut commands, transform.translation, direction.get());
-
-                if grounded.check() {
-                    buffer.block_all();
-                    velocity.x = 0.;
-                } else {
-                    buffer.block(Blocker::non_directional());
-                }
+                (behavior.bullet.clone(), AttackKind::Shot)
             }
             Stage::Stall if timer_finished => {
                 behavior.set_stage(Stage::Dormant);
                 buffer.clear_blocker();
+                continue;
             }
-            _ => {}
+            _ => continue,
+        };
+
+        behavior.charge_timer.reset();
+
+        let Some(kind) = projectile_types.get(&kind) else {
+            continue;
+        };
+        if !Energy::affords(o_energy.as_deref(), kind.energy_cost) {
+            continue;
+        }
+        if let Some(energy) = o_energy.as_mut() {
+            energy.spend(kind.energy_cost);
+        }
+
+        behavior.set_stage(Stage::Stall);
+        Shot::spawn_projectile(
+            &mut commands,
+            transform.translation,
+            direction.get(),
+            kind,
+            faction.targets(),
+            attack,
+        );
+
+        if grounded.check() {
+            buffer.block_all();
+            velocity.x = 0.;
+        } else {
+            buffer.block(Blocker::non_directional());
         }
     }
 }
@@ -129,6 +325,14 @@ pub struct ShotBehavior;
 
 impl Plugin for ShotBehavior {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (shot_player_behavior, projectile_behavior));
+        app.init_asset::<ProjectileType>().add_systems(
+            FixedUpdate,
+            (
+                shot_player_behavior
+                    .in_set(BehaviorSet::Attacks)
+                    .after(demo_slash_player_behavior),
+                projectile_behavior.in_set(GameplaySet::Movement),
+            ),
+        );
     }
 }
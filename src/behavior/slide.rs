@@ -3,14 +3,18 @@ use std::time::Duration;
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::collision_groups::{CollisionGroup, ENEMY};
+use crate::abilities::components::{Abilities, Ability};
+use crate::characters::components::{Body, Faction};
+use crate::collision_groups::CollisionGroup;
 use crate::input::buffer::InputBuffer;
-use crate::input::inputs::Inputs;
-use crate::player::components::{Body, Player};
 use crate::shape_intersections::ShapeIntersections;
+use crate::state::energy::Energy;
 use crate::state::facing_direction::FacingDirection;
+use crate::state::grounded::{Ground, Grounded};
+use crate::system_sets::BehaviorSet;
 
-use super::crouch::Crouch;
+use super::crouch::{crouching_behavior_player, Crouch};
+use super::{AttackKind, BehaviorInput, Struck};
 
 #[derive(Component)]
 pub struct Slide {
@@ -18,6 +22,10 @@ pub struct Slide {
     stage: Stage,
     has_hit: bool,
     speed: f32,
+    // how fast the slide picks up speed going down the steepest slope, it keeps going until the slope ends
+    downhill_acceleration: f32,
+    current_speed: f32,
+    energy_cost: f32,
 }
 
 pub enum Stage {
@@ -27,12 +35,15 @@ pub enum Stage {
 }
 
 impl Slide {
-    pub fn new(speed: f32) -> Self {
+    pub fn new(speed: f32, downhill_acceleration: f32, energy_cost: f32) -> Self {
         Self {
             stage_timer: Timer::from_seconds(0., TimerMode::Once),
             stage: Stage::Dormant,
             has_hit: false,
             speed,
+            downhill_acceleration,
+            current_speed: speed,
+            energy_cost,
         }
     }
 
@@ -40,10 +51,15 @@ impl Slide {
         !matches!(&self.stage, Stage::Dormant)
     }
 
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
     pub fn set_stage(&mut self, stage: Stage) {
         match stage {
             Stage::Accelerate { .. } => {
                 self.has_hit = false;
+                self.current_speed = self.speed;
                 self.stage_timer.set_duration(Duration::from_secs_f32(0.15));
                 self.stage_timer.reset();
             }
@@ -55,49 +71,91 @@ impl Slide {
         }
         self.stage = stage;
     }
+
+    /// Picks up speed on a downhill slope, `downhill` being the sine of its angle.
+    fn accelerate(&mut self, downhill: f32, delta: f32) {
+        self.current_speed += self.downhill_acceleration * downhill * delta;
+    }
+
+    /// Velocity for sliding in `direction`, along the ground if there's any under us.
+    fn velocity(&self, footing: Option<&Ground>, direction: f32, vertical: f32) -> Vec2 {
+        let speed = self.current_speed * direction;
+
+        footing.map_or(Vec2::new(speed, vertical), |ground| ground.along(speed))
+    }
 }
 
-fn sliding_handler_player(
-    mut q_player: Query<
-        (
-            &mut LinearVelocity,
-            &mut InputBuffer,
-            &FacingDirection,
-            &Crouch,
-            &Body,
-            &mut Slide,
-            &Transform,
-        ),
-        With<Player>,
-    >,
+pub fn sliding_handler_player(
+    mut q_player: Query<(
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &FacingDirection,
+        &Crouch,
+        &Body,
+        &Grounded,
+        &mut Slide,
+        &Transform,
+        &Faction,
+        &BehaviorInput<Slide>,
+        Option<&Abilities>,
+        Option<&mut Energy>,
+    )>,
     time: Res<Time>,
     mut shape_intersections: ShapeIntersections,
+    q_layers: Query<&CollisionLayers>,
+    mut struck: EventWriter<Struck>,
 ) {
-    for (mut velocity, mut buffer, direction, crouching, body, mut state, transform) in
-        q_player.iter_mut()
+    for (
+        mut velocity,
+        mut buffer,
+        direction,
+        crouching,
+        body,
+        grounded,
+        mut state,
+        transform,
+        faction,
+        binding,
+        o_abilities,
+        mut o_energy,
+    ) in q_player.iter_mut()
     {
         let timer_finished = state.stage_timer.tick(time.delta()).finished();
 
+        // Sliding down a slope picks up speed and lasts until we reach the bottom
+        let footing = grounded.footing(velocity.0);
+        let downhill = footing
+            .filter(|ground| ground.tangent().y * direction.get() < 0.)
+            .map_or(0., |ground| ground.slope().sin());
+
         match state.stage {
             Stage::Dormant
                 if crouching.check()
+                    && Abilities::allows(o_abilities, Ability::Slide)
+                    && Energy::affords(o_energy.as_deref(), state.energy_cost)
                     && buffer
                         .query()
-                        .contains(Inputs::Jump.just_pressed())
+                        .contains(binding.get().just_pressed())
                         .within_timeframe(Duration::from_millis(200))
                         .consume() =>
             {
+                if let Some(energy) = o_energy.as_mut() {
+                    energy.spend(state.energy_cost);
+                }
+
                 buffer.block_all();
                 state.set_stage(Stage::Accelerate);
             }
-            Stage::Accelerate if timer_finished => {
+            Stage::Accelerate if timer_finished && downhill <= 0. => {
                 state.set_stage(Stage::Settle);
             }
             Stage::Accelerate if state.has_hit => {
-                velocity.x = state.speed * direction.get();
+                state.accelerate(downhill, time.delta_seconds());
+                velocity.0 = state.velocity(footing, direction.get(), velocity.y);
             }
             Stage::Accelerate => {
-                velocity.x = state.speed * direction.get();
+                state.accelerate(downhill, time.delta_seconds());
+                velocity.0 = state.velocity(footing, direction.get(), velocity.y);
 
                 if let Some(other) = shape_intersections
                     .shape_intersections(
@@ -105,11 +163,20 @@ fn sliding_handler_player(
                         transform.translation.xy()
                             + Vec2::new(body.height / 4. * direction.get(), -body.height / 3.),
                         0.,
-                        CollisionGroup::filter(ENEMY),
+                        CollisionGroup::targets(*faction),
                     )
                     .first()
                 {
                     println!("Slide-kicked into: {other:?}");
+                    struck.send(Struck {
+                        entity: *other,
+                        attack: AttackKind::Slide,
+                    });
+                    Energy::gain_from_hit(
+                        o_energy.as_deref_mut(),
+                        *faction,
+                        q_layers.get(*other).ok(),
+                    );
                     state.has_hit = true;
                 }
             }
@@ -126,6 +193,11 @@ pub struct SlidingBehavior;
 
 impl Plugin for SlidingBehavior {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, sliding_handler_player);
+        app.add_systems(
+            FixedUpdate,
+            sliding_handler_player
+                .in_set(BehaviorSet::Stance)
+                .after(crouching_behavior_player),
+        );
     }
 }
@@ -0,0 +1,116 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::abilities::components::{Abilities, Ability};
+use crate::input::blocker::Blocker;
+use crate::input::buffer::InputBuffer;
+use crate::state::submerged::Submerged;
+use crate::system_sets::BehaviorSet;
+
+use super::crouch::Crouch;
+
+// Swimming starts once this much of the body is underwater, and stops below the exit depth
+const ENTER_SUBMERSION: f32 = 0.5;
+const EXIT_SUBMERSION: f32 = 0.25;
+// Anything shallower than this counts as being at the surface, where jumping out is allowed
+const SURFACE_SUBMERSION: f32 = 0.95;
+
+#[derive(Component)]
+pub struct Swim {
+    stage: Stage,
+    speed: f32,
+    acceleration: f32,
+    // fraction of the jump force available when jumping out of the water
+    surface_jump_factor: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    Swimming,
+}
+
+impl Swim {
+    pub fn new(speed: f32, acceleration: f32, surface_jump_factor: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            speed,
+            acceleration,
+            surface_jump_factor,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        matches!(self.stage, Stage::Swimming)
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        self.stage = stage;
+    }
+
+    /// Checks if the swimmer is close enough to the surface to jump out.
+    pub fn at_surface(&self, submerged: &Submerged) -> bool {
+        self.check() && submerged.submersion() < SURFACE_SUBMERSION
+    }
+
+    pub fn surface_jump_factor(&self) -> f32 {
+        self.surface_jump_factor
+    }
+}
+
+pub fn swimming_behavior_player(
+    mut q_player: Query<(
+        &mut LinearVelocity,
+        &mut InputBuffer,
+        &mut Swim,
+        &Submerged,
+        Option<&Crouch>,
+        Option<&Abilities>,
+    )>,
+    time: Res<Time>,
+) {
+    for (mut vel, mut buffer, mut state, submerged, o_crouch, o_abilities) in q_player.iter_mut() {
+        // Directions are blocked for other behaviors while swimming, so we read the raw frame
+        let frame = buffer.this_frame();
+
+        match state.stage {
+            Stage::Dormant
+                if submerged.submersion() > ENTER_SUBMERSION
+                    && !o_crouch.is_some_and(Crouch::check)
+                    && Abilities::allows(o_abilities, Ability::Swim) =>
+            {
+                buffer.block(Blocker::directions());
+                state.set_stage(Stage::Swimming);
+            }
+            Stage::Swimming if !submerged.check() || submerged.submersion() < EXIT_SUBMERSION => {
+                buffer.clear_blocker();
+                state.set_stage(Stage::Dormant);
+            }
+            Stage::Swimming => {
+                // Other behaviors clear the blocker when they finish, so keep it up while we swim
+                buffer.block(Blocker::directions());
+
+                let target = frame.direction().as_vec2() * state.speed;
+                let blend = (state.acceleration * time.delta_seconds()).min(1.);
+
+                vel.x += (target.x - vel.x) * blend;
+
+                // With no vertical input buoyancy takes over
+                if target.y.abs() > 0. {
+                    vel.y += (target.y - vel.y) * blend;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct SwimBehavior;
+
+impl Plugin for SwimBehavior {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            swimming_behavior_player.in_set(BehaviorSet::Traversal),
+        );
+    }
+}
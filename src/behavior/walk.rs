@@ -6,7 +6,8 @@ use bevy::prelude::*;
 
 use crate::{
     input::{buffer::InputBuffer, directions::InputDirection, inputs::Inputs},
-    player::components::Player,
+    state::grounded::{Ground, Grounded},
+    system_sets::BehaviorSet,
 };
 
 use super::crouch::Crouch;
@@ -17,6 +18,9 @@ pub struct Walk {
     slowing_factor: f32,
     max_speed: f32,
     acceleration_factor: f32,
+    // multiply max speed on the steepest walkable slopes, scaling in from 1 on flat ground
+    uphill_factor: f32,
+    downhill_factor: f32,
 }
 
 pub enum Stage {
@@ -26,41 +30,101 @@ pub enum Stage {
 }
 
 impl Walk {
-    pub fn new(slowing_factor: f32, max_speed: f32, acceleration_factor: f32) -> Self {
+    pub fn new(
+        slowing_factor: f32,
+        max_speed: f32,
+        acceleration_factor: f32,
+        uphill_factor: f32,
+        downhill_factor: f32,
+    ) -> Self {
         Self {
             stage: Stage::Dormant,
             slowing_factor,
             max_speed,
             acceleration_factor,
+            uphill_factor,
+            downhill_factor,
         }
     }
 
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
     pub fn set_stage(&mut self, stage: Stage) {
         self.stage = stage;
     }
+
+    /// Max speed when walking in `direction` over `ground`.
+    fn max_speed_on(&self, ground: &Ground, direction: f32, max_slope: f32) -> f32 {
+        let climb = ground.tangent().y * direction;
+        let factor = if climb > 0. {
+            self.uphill_factor
+        } else if climb < 0. {
+            self.downhill_factor
+        } else {
+            return self.max_speed;
+        };
+
+        let steepness = (ground.slope() / max_slope).min(1.);
+        self.max_speed * (1. + (factor - 1.) * steepness)
+    }
+}
+
+/// Sets the speed along the ground if there is any under us, otherwise just the horizontal speed.
+fn set_ground_speed(vel: &mut LinearVelocity, footing: Option<&Ground>, speed: f32) {
+    match footing {
+        Some(ground) => vel.0 = ground.along(speed),
+        None => vel.x = speed,
+    }
 }
 
 pub fn walking_behavior_player(
-    mut q_player: Query<(&mut LinearVelocity, &Crouch, &InputBuffer, &Walk), With<Player>>,
+    mut q_player: Query<(
+        &mut LinearVelocity,
+        &Crouch,
+        &Grounded,
+        &InputBuffer,
+        &mut Walk,
+    )>,
     time: Res<Time>,
 ) {
-    for (mut vel, crouching, buffer, state) in q_player.iter_mut() {
+    for (mut vel, crouching, grounded, buffer, mut state) in q_player.iter_mut() {
         let x_input = buffer.this_frame().x();
 
+        // On the ground we walk along its surface, so slopes don't turn our speed into a skid or a launch
+        let footing = grounded.footing(vel.0);
+        let mut speed = footing.map_or(vel.x, |ground| vel.dot(ground.tangent()));
+
         if !(x_input.abs() > 0.2)
-            || vel.x.signum() * x_input.signum() < 0.
+            || speed.signum() * x_input.signum() < 0.
             || buffer.blocked(Inputs::Directional)
         {
-            vel.x -= vel.x * state.slowing_factor * time.delta_seconds();
+            speed -= speed * state.slowing_factor * time.delta_seconds();
         }
 
         if crouching.check() || buffer.blocked(InputDirection::Down) {
-            return;
+            set_ground_speed(&mut vel, footing, speed);
+            state.set_stage(Stage::Dormant);
+            continue;
         }
 
-        vel.x = (vel.x
+        if x_input.abs() > 0.2 && !buffer.blocked(Inputs::Directional) {
+            state.set_stage(Stage::Active);
+        } else if speed.abs() > 1. {
+            state.set_stage(Stage::Slowing);
+        } else {
+            state.set_stage(Stage::Dormant);
+        }
+
+        let max_speed = footing.map_or(state.max_speed, |ground| {
+            state.max_speed_on(ground, x_input, grounded.max_slope())
+        });
+        speed = (speed
             + x_input * state.max_speed * state.acceleration_factor * time.delta_seconds())
-        .clamp(-state.max_speed, state.max_speed);
+        .clamp(-max_speed, max_speed);
+
+        set_ground_speed(&mut vel, footing, speed);
     }
 }
 
@@ -68,6 +132,6 @@ pub struct WalkBehavior;
 
 impl Plugin for WalkBehavior {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, walking_behavior_player);
+        app.add_systems(FixedUpdate, walking_behavior_player.in_set(BehaviorSet::Locomotion));
     }
 }
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::behavior::AttackKind;
+
+/// Environment that comes down after enough hits from the right kind of attack.
+///
+/// Once broken it stays broken, its flag is kept in the `PersistentState`.
+#[derive(Component)]
+pub struct Breakable {
+    id: &'static str,
+    size: Vec2,
+    // hits left before it breaks
+    health: u32,
+    // only these attacks do anything to it
+    breaks_with: Vec<AttackKind>,
+}
+
+impl Breakable {
+    pub fn new(id: &'static str, size: Vec2, health: u32, breaks_with: Vec<AttackKind>) -> Self {
+        Self {
+            id,
+            size,
+            health,
+            breaks_with,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Returns the flag used to remember this breakable is broken in the `PersistentState`.
+    pub fn flag(&self) -> String {
+        format!("broken_{}", self.id)
+    }
+
+    /// Takes a hit from `attack`, returning whether that broke it.
+    pub fn strike(&mut self, attack: AttackKind) -> bool {
+        if !self.breaks_with.contains(&attack) {
+            return false;
+        }
+
+        self.health = self.health.saturating_sub(1);
+        self.health == 0
+    }
+}
+
+/// A piece of a broken breakable, gone after a little while.
+#[derive(Component)]
+pub struct Debris {
+    lifetime: Timer,
+}
+
+impl Debris {
+    pub fn new(lifetime: f32) -> Self {
+        Self {
+            lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+        }
+    }
+
+    /// Ticks the lifetime, returning whether it's over.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.lifetime.tick(delta).finished()
+    }
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::behavior::AttackKind;
+
+/// Sent when a breakable comes down, just before it's despawned.
+#[derive(Event)]
+pub struct Broken {
+    pub entity: Entity,
+    pub id: &'static str,
+    pub attack: AttackKind,
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::system_sets::GameplaySet;
+
+use self::{
+    events::Broken,
+    systems::{clear_debris, strike_breakables},
+};
+
+pub mod components;
+pub mod events;
+mod systems;
+
+pub struct BreakablesPlugin;
+
+impl Plugin for BreakablesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Broken>().add_systems(
+            FixedUpdate,
+            (strike_breakables, clear_debris)
+                .chain()
+                .in_set(GameplaySet::HitResolution),
+        );
+    }
+}
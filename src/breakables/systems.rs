@@ -0,0 +1,86 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    behavior::Struck, collision_groups::CollisionGroup, persistence::resources::PersistentState,
+};
+
+use super::{
+    components::{Breakable, Debris},
+    events::Broken,
+};
+
+// debris pieces per side of a broken breakable, and how long they stay around
+const DEBRIS_PER_SIDE: u32 = 3;
+const DEBRIS_LIFETIME: f32 = 1.5;
+
+/// Applies hits to breakables, bringing down the ones that run out of health.
+pub fn strike_breakables(
+    mut commands: Commands,
+    mut struck: EventReader<Struck>,
+    mut q_breakable: Query<(&mut Breakable, &Transform)>,
+    mut broken: EventWriter<Broken>,
+    mut state: ResMut<PersistentState>,
+) {
+    for event in struck.read() {
+        let Ok((mut breakable, transform)) = q_breakable.get_mut(event.entity) else {
+            continue;
+        };
+
+        if !breakable.strike(event.attack) {
+            continue;
+        }
+
+        broken.send(Broken {
+            entity: event.entity,
+            id: breakable.id(),
+            attack: event.attack,
+        });
+        state.set(breakable.flag());
+
+        spawn_debris(&mut commands, transform.translation.xy(), breakable.size());
+        commands.entity(event.entity).despawn_recursive();
+    }
+}
+
+/// Scatters a grid of pieces the size of the broken breakable, flying out from its center.
+fn spawn_debris(commands: &mut Commands, center: Vec2, size: Vec2) {
+    let piece = size / DEBRIS_PER_SIDE as f32;
+
+    for x in 0..DEBRIS_PER_SIDE {
+        for y in 0..DEBRIS_PER_SIDE {
+            let offset = (Vec2::new(x as f32, y as f32) + 0.5) * piece - size / 2.;
+
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::srgb(0.45, 0.4, 0.35),
+                        custom_size: Some(piece * 0.8),
+                        ..default()
+                    },
+                    transform: Transform::from_translation((center + offset).extend(0.)),
+                    ..default()
+                },
+                CollisionGroup::DEBRIS,
+                RigidBody::Dynamic,
+                Collider::rectangle(piece.x * 0.8, piece.y * 0.8),
+                LinearVelocity(offset.normalize_or_zero() * 250. + Vec2::Y * 150.),
+                AngularVelocity(offset.x.signum() * -4.),
+                Debris::new(DEBRIS_LIFETIME),
+                Name::new("Debris"),
+            ));
+        }
+    }
+}
+
+pub fn clear_debris(
+    mut commands: Commands,
+    mut q_debris: Query<(Entity, &mut Debris)>,
+    time: Res<Time>,
+) {
+    for (entity, mut debris) in q_debris.iter_mut() {
+        if debris.tick(time.delta()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
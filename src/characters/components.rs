@@ -0,0 +1,228 @@
+use std::{collections::HashMap, time::Duration};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::collision_groups::{BREAKABLE, ENEMY, PLAYER};
+
+/// The standing size of a character, and the collider it currently moves with.
+#[derive(Component)]
+pub struct Body {
+    pub height: f32,
+    pub width: f32,
+    pub collider_ref: Entity,
+}
+
+/// Which side a character fights on, deciding what its attacks can hit.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Faction {
+    Player,
+    Enemy,
+}
+
+impl Faction {
+    /// The hurtbox layer of characters in this faction.
+    pub fn layer(self) -> LayerMask {
+        match self {
+            Faction::Player => PLAYER,
+            Faction::Enemy => ENEMY,
+        }
+    }
+
+    /// The hurtbox layer of the characters this faction fights.
+    pub fn hostile(self) -> LayerMask {
+        match self {
+            Faction::Player => ENEMY,
+            Faction::Enemy => PLAYER,
+        }
+    }
+
+    /// Whether something on `layers` is the hurtbox of a character this faction fights.
+    pub fn is_hostile(self, layers: &CollisionLayers) -> bool {
+        (layers.memberships & self.hostile()).0 != 0
+    }
+
+    /// The layers this faction's attacks hit, the other side's hurtboxes and, for the player,
+    /// breakable environment.
+    pub fn targets(self) -> LayerMask {
+        match self {
+            Faction::Player => self.hostile() | BREAKABLE,
+            Faction::Enemy => self.hostile(),
+        }
+    }
+}
+
+/// Everything a character can be seen doing, each mapped to a clip by its `Animator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationState {
+    Idle,
+    Walk,
+    Jump,
+    Fall,
+    Crouch,
+    Crawl,
+    Slide,
+    SlashWindup,
+    SlashActive,
+    SlashSettle,
+    Kick,
+    Hang,
+    Climb,
+    Swim,
+    Swing,
+}
+
+/// A sequence of frames, with named events fired when certain frames are reached.
+#[derive(Clone)]
+pub struct AnimationClip {
+    frames: Vec<Handle<Image>>,
+    frame_time: Duration,
+    looping: bool,
+    events: Vec<(usize, &'static str)>,
+}
+
+impl AnimationClip {
+    pub fn new(
+        frames: Vec<Handle<Image>>,
+        fps: f32,
+        looping: bool,
+        events: Vec<(usize, &'static str)>,
+    ) -> Self {
+        Self {
+            frames,
+            frame_time: Duration::from_secs_f32(1. / fps),
+            looping,
+            events,
+        }
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&Handle<Image>> {
+        self.frames.get(index)
+    }
+
+    /// Returns the names of the events fired on entering frame `index`.
+    pub fn events_at(&self, index: usize) -> impl Iterator<Item = &'static str> + '_ {
+        self.events
+            .iter()
+            .filter(move |(frame, _)| *frame == index)
+            .map(|(_, name)| *name)
+    }
+
+    /// Returns the frame after `index`, or `None` once a non looping clip has finished.
+    fn next(&self, index: usize) -> Option<usize> {
+        match index + 1 {
+            next if next < self.frames.len() => Some(next),
+            _ if self.looping => Some(0),
+            _ => None,
+        }
+    }
+}
+
+/// Plays a character's clips, switching between them as its `AnimationState` changes.
+#[derive(Component)]
+pub struct Animator {
+    clips: HashMap<AnimationState, AnimationClip>,
+    // played for any state without a clip of its own
+    fallback: AnimationState,
+    state: AnimationState,
+    frame: usize,
+    frame_timer: Timer,
+    // set when a clip starts, so its first frame is reported like any other
+    started: bool,
+}
+
+impl Animator {
+    pub fn new(clips: HashMap<AnimationState, AnimationClip>, fallback: AnimationState) -> Self {
+        let frame_time = clips
+            .get(&fallback)
+            .map_or(Duration::ZERO, |clip| clip.frame_time);
+
+        Self {
+            clips,
+            fallback,
+            state: fallback,
+            frame: 0,
+            frame_timer: Timer::new(frame_time, TimerMode::Once),
+            started: true,
+        }
+    }
+
+    /// Placeholder clips for every state, cut out of a walk cycle until each state gets its own art.
+    pub fn from_walk_cycle(walk: &[Handle<Image>]) -> Self {
+        let still = |index: usize| {
+            let frame = walk.iter().skip(index).take(1).cloned().collect();
+            AnimationClip::new(frame, 1., false, vec![])
+        };
+        let cycle = |fps: f32, events: Vec<(usize, &'static str)>| {
+            AnimationClip::new(walk.to_vec(), fps, true, events)
+        };
+
+        Animator::new(
+            HashMap::from([
+                (AnimationState::Idle, still(0)),
+                (
+                    AnimationState::Walk,
+                    cycle(12., vec![(1, "footstep"), (5, "footstep")]),
+                ),
+                (AnimationState::Jump, still(2)),
+                (AnimationState::Fall, still(6)),
+                (AnimationState::Crouch, still(4)),
+                (AnimationState::Crawl, cycle(6., vec![])),
+                (AnimationState::Slide, still(3)),
+                (AnimationState::SlashWindup, still(7)),
+                (AnimationState::SlashActive, still(1)),
+                (AnimationState::SlashSettle, still(0)),
+                (AnimationState::Kick, still(5)),
+                (AnimationState::Hang, still(4)),
+                (AnimationState::Climb, cycle(8., vec![])),
+                (AnimationState::Swim, cycle(4., vec![])),
+                (AnimationState::Swing, still(3)),
+            ]),
+            AnimationState::Idle,
+        )
+    }
+
+    pub fn state(&self) -> AnimationState {
+        self.state
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Switches to `state`, restarting from the first frame if it changed.
+    pub fn set_state(&mut self, state: AnimationState) {
+        if self.state == state {
+            return;
+        }
+
+        self.state = state;
+        self.frame = 0;
+        let frame_time = self.clip().map_or(Duration::ZERO, |clip| clip.frame_time);
+        self.frame_timer = Timer::new(frame_time, TimerMode::Once);
+        self.started = true;
+    }
+
+    pub fn clip(&self) -> Option<&AnimationClip> {
+        self.clips
+            .get(&self.state)
+            .or_else(|| self.clips.get(&self.fallback))
+    }
+
+    /// Advances the current clip, returning the new frame index when it changes.
+    pub fn tick(&mut self, delta: Duration) -> Option<usize> {
+        if self.started {
+            self.started = false;
+            return Some(self.frame);
+        }
+
+        if !self.frame_timer.tick(delta).finished() {
+            return None;
+        }
+
+        let next = self.clip()?.next(self.frame)?;
+        self.frame = next;
+        self.frame_timer.reset();
+        Some(next)
+    }
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+use super::components::AnimationState;
+
+/// Sent when an animation reaches a frame tagged with an event, like a footstep or the swing of an attack.
+#[derive(Event)]
+pub struct AnimationFrameEvent {
+    pub entity: Entity,
+    pub state: AnimationState,
+    pub frame: usize,
+    pub name: &'static str,
+}
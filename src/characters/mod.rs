@@ -1,13 +1,15 @@
 use bevy::prelude::*;
-use bevy_asset_loader::loading_state::{
-    config::{ConfigureLoadingState, LoadingStateConfig},
-    LoadingStateAppExt,
-};
 
-use crate::GameState;
+use crate::loading::collection::LoadingAppExt;
 
-use self::resources::PlayerWalk;
+use self::{
+    events::AnimationFrameEvent,
+    resources::PlayerWalk,
+    systems::{flip_sprites, play_animations, select_animation},
+};
 
+pub mod components;
+pub mod events;
 pub mod resources;
 mod systems;
 
@@ -15,8 +17,12 @@ pub struct CharactersPlugin;
 
 impl Plugin for CharactersPlugin {
     fn build(&self, app: &mut App) {
-        app.configure_loading_state(
-            LoadingStateConfig::new(GameState::Loading).load_collection::<PlayerWalk>(),
-        );
+        // Animations only follow the simulation, so they run every frame rather than every tick
+        app.load_collection::<PlayerWalk>()
+            .add_event::<AnimationFrameEvent>()
+            .add_systems(
+                Update,
+                (select_animation, play_animations, flip_sprites).chain(),
+            );
     }
 }
@@ -1,20 +1,35 @@
 use bevy::prelude::*;
-use bevy_asset_loader::asset_collection::AssetCollection;
 
-#[derive(AssetCollection, Resource)]
+use crate::loading::collection::AssetCollection;
+
+#[derive(Resource)]
 pub struct PlayerWalk {
-    #[asset(
-        paths(
-            "p_walk/1.png",
-            "p_walk/2.png",
-            "p_walk/3.png",
-            "p_walk/4.png",
-            "p_walk/5.png",
-            "p_walk/6.png",
-            "p_walk/7.png",
-            "p_walk/8.png"
-        ),
-        collection(typed)
-    )]
     walking: Vec<Handle<Image>>,
 }
+
+impl PlayerWalk {
+    pub fn frames(&self) -> Vec<Handle<Image>> {
+        self.walking.clone()
+    }
+}
+
+impl FromWorld for PlayerWalk {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+
+        Self {
+            walking: (1..=8)
+                .map(|frame| asset_server.load(format!("demoplayer/walk/{frame}.png")))
+                .collect(),
+        }
+    }
+}
+
+impl AssetCollection for PlayerWalk {
+    fn handles(&self) -> Vec<UntypedHandle> {
+        self.walking
+            .iter()
+            .map(|handle| handle.clone().untyped())
+            .collect()
+    }
+}
@@ -1 +1,126 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
 
+use crate::{
+    behavior::{
+        climb::Climb,
+        crawl::Crawl,
+        crouch::Crouch,
+        demo_slash::{self, DemoSlash},
+        grapple::Grapple,
+        kick::{self, Kick},
+        ledge_grab::{self, LedgeGrab},
+        slide::{self, Slide},
+        swim::Swim,
+        walk::{self, Walk},
+    },
+    state::{facing_direction::FacingDirection, grounded::Grounded},
+};
+
+use super::{
+    components::{AnimationState, Animator},
+    events::AnimationFrameEvent,
+};
+
+/// Picks the animation for whatever the character is doing, the first matching behavior wins.
+pub fn select_animation(
+    mut q_animator: Query<(
+        &mut Animator,
+        &Grounded,
+        &LinearVelocity,
+        Option<&Walk>,
+        Option<&Crouch>,
+        Option<&Crawl>,
+        Option<&Slide>,
+        Option<&DemoSlash>,
+        Option<&Kick>,
+        Option<&LedgeGrab>,
+        Option<&Climb>,
+        Option<&Grapple>,
+        Option<&Swim>,
+    )>,
+) {
+    for (
+        mut animator,
+        grounded,
+        vel,
+        o_walk,
+        o_crouch,
+        o_crawl,
+        o_slide,
+        o_slash,
+        o_kick,
+        o_ledge_grab,
+        o_climb,
+        o_grapple,
+        o_swim,
+    ) in q_animator.iter_mut()
+    {
+        let state = match (
+            o_slash.map(DemoSlash::stage),
+            o_slide.map(Slide::stage),
+            o_kick.map(Kick::stage),
+            o_ledge_grab.map(LedgeGrab::stage),
+        ) {
+            (Some(demo_slash::Stage::Windup), ..) => AnimationState::SlashWindup,
+            (Some(demo_slash::Stage::Active), ..) => AnimationState::SlashActive,
+            (Some(demo_slash::Stage::Settle), ..) => AnimationState::SlashSettle,
+            (_, Some(slide::Stage::Accelerate), ..) => AnimationState::Slide,
+            (_, Some(slide::Stage::Settle), ..) => AnimationState::Crouch,
+            (_, _, Some(kick::Stage::Active), _) => AnimationState::Kick,
+            (.., Some(ledge_grab::Stage::Hanging)) => AnimationState::Hang,
+            (.., Some(ledge_grab::Stage::Climbing { .. })) => AnimationState::Climb,
+            _ if o_climb.is_some_and(Climb::check) => AnimationState::Climb,
+            _ if o_grapple.is_some_and(Grapple::check) => AnimationState::Swing,
+            _ if o_swim.is_some_and(Swim::check) => AnimationState::Swim,
+            _ if o_crawl.is_some_and(Crawl::check) => AnimationState::Crawl,
+            _ if o_crouch.is_some_and(Crouch::check) => AnimationState::Crouch,
+            _ if !grounded.check() && vel.y > 0. => AnimationState::Jump,
+            _ if !grounded.check() => AnimationState::Fall,
+            _ if o_walk.is_some_and(|walk| matches!(walk.stage(), walk::Stage::Active)) => {
+                AnimationState::Walk
+            }
+            _ => AnimationState::Idle,
+        };
+
+        animator.set_state(state);
+    }
+}
+
+/// Steps every animator, showing its current frame and sending the events tagged on it.
+pub fn play_animations(
+    mut q_animator: Query<(Entity, &mut Animator, &mut Handle<Image>)>,
+    mut frame_events: EventWriter<AnimationFrameEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut animator, mut texture) in q_animator.iter_mut() {
+        let Some(frame) = animator.tick(time.delta()) else {
+            continue;
+        };
+        let Some(clip) = animator.clip() else {
+            continue;
+        };
+
+        if let Some(image) = clip.frame(frame) {
+            texture.clone_from(image);
+        }
+
+        for name in clip.events_at(frame) {
+            frame_events.send(AnimationFrameEvent {
+                entity,
+                state: animator.state(),
+                frame,
+                name,
+            });
+        }
+    }
+}
+
+/// Mirrors sprites to match the way their character is facing.
+pub fn flip_sprites(
+    mut q_sprite: Query<(&mut Sprite, &FacingDirection), Changed<FacingDirection>>,
+) {
+    for (mut sprite, direction) in q_sprite.iter_mut() {
+        sprite.flip_x = direction.get() < 0.;
+    }
+}
@@ -2,35 +2,157 @@ use avian2d::{
     collision::{CollisionLayers, LayerMask},
     spatial_query::SpatialQueryFilter,
 };
+use bevy::prelude::*;
+
+use crate::characters::components::Faction;
+
+/// Every collision layer in the game, each one gets its own bit.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Layer {
+    /// Colliders that are switched off, like the standing collider while crouched.
+    Inactive,
+    /// Player hurtboxes.
+    Player,
+    /// Enemy hurtboxes.
+    Enemy,
+    /// Solid level geometry.
+    Environment,
+    /// The colliders characters move with.
+    Collider,
+    Climbable,
+    Water,
+    Grapple,
+    Hazard,
+    OneWay,
+    Projectile,
+    Trigger,
+    /// Pickups and anything else the player only has to touch.
+    Interactable,
+    /// Environment that the player's attacks can break.
+    Breakable,
+    /// What's left of broken environment, it only lands on the level.
+    Debris,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 15] = [
+        Layer::Inactive,
+        Layer::Player,
+        Layer::Enemy,
+        Layer::Environment,
+        Layer::Collider,
+        Layer::Climbable,
+        Layer::Water,
+        Layer::Grapple,
+        Layer::Hazard,
+        Layer::OneWay,
+        Layer::Projectile,
+        Layer::Trigger,
+        Layer::Interactable,
+        Layer::Breakable,
+        Layer::Debris,
+    ];
+
+    pub const fn mask(self) -> LayerMask {
+        LayerMask(1 << self as u32)
+    }
+}
+
+/// Which layers collide with each other, both ways, so each pair only needs listing once.
+///
+/// Layers that are only ever found through spatial queries, like hurtboxes or water, don't
+/// collide with anything and are left out. Interactables are the exception, they only ever
+/// overlap the player's hurtbox, whose own filters stay empty.
+const INTERACTIONS: &[(Layer, Layer)] = &[
+    (Layer::Collider, Layer::Environment),
+    (Layer::Collider, Layer::Hazard),
+    (Layer::Collider, Layer::OneWay),
+    (Layer::Collider, Layer::Trigger),
+    (Layer::Interactable, Layer::Player),
+    (Layer::Projectile, Layer::Environment),
+    (Layer::Debris, Layer::Environment),
+];
 
 const NONE: LayerMask = LayerMask(0);
-const INACTIVE: LayerMask = LayerMask(1);
-pub const PLAYER: LayerMask = LayerMask(1 << 1);
-pub const ENEMY: LayerMask = LayerMask(1 << 2);
-pub const ENVIRONMENT: LayerMask = LayerMask(1 << 3);
-const COLLIDER: LayerMask = LayerMask(1 << 4);
+pub const PLAYER: LayerMask = Layer::Player.mask();
+pub const ENEMY: LayerMask = Layer::Enemy.mask();
+pub const ENVIRONMENT: LayerMask = Layer::Environment.mask();
+pub const COLLIDER: LayerMask = Layer::Collider.mask();
+pub const CLIMBABLE: LayerMask = Layer::Climbable.mask();
+pub const WATER: LayerMask = Layer::Water.mask();
+pub const GRAPPLE: LayerMask = Layer::Grapple.mask();
+pub const HAZARD: LayerMask = Layer::Hazard.mask();
+pub const ONE_WAY: LayerMask = Layer::OneWay.mask();
+pub const PROJECTILE: LayerMask = Layer::Projectile.mask();
+pub const TRIGGER: LayerMask = Layer::Trigger.mask();
+pub const INTERACTABLE: LayerMask = Layer::Interactable.mask();
+pub const BREAKABLE: LayerMask = Layer::Breakable.mask();
 
 pub struct CollisionGroup;
 
 impl CollisionGroup {
-    pub const INACTIVE: CollisionLayers = CollisionLayers {
-        memberships: INACTIVE,
-        filters: NONE,
-    };
-
-    pub const ENVIRONMENT: CollisionLayers = CollisionLayers {
-        memberships: ENVIRONMENT,
-        filters: COLLIDER,
-    };
-
-    pub const COLLIDER: CollisionLayers = CollisionLayers {
-        memberships: COLLIDER,
-        filters: ENVIRONMENT,
-    };
-
-    pub fn hurtbox(groups: LayerMask) -> CollisionLayers {
+    pub const INACTIVE: CollisionLayers = CollisionGroup::of(&[Layer::Inactive]);
+    pub const ENVIRONMENT: CollisionLayers = CollisionGroup::of(&[Layer::Environment]);
+    pub const COLLIDER: CollisionLayers = CollisionGroup::of(&[Layer::Collider]);
+    pub const CLIMBABLE: CollisionLayers = CollisionGroup::of(&[Layer::Climbable]);
+    pub const WATER: CollisionLayers = CollisionGroup::of(&[Layer::Water]);
+    pub const GRAPPLE: CollisionLayers = CollisionGroup::of(&[Layer::Grapple]);
+    pub const PROJECTILE: CollisionLayers = CollisionGroup::of(&[Layer::Projectile]);
+    pub const TRIGGER: CollisionLayers = CollisionGroup::of(&[Layer::Trigger]);
+    pub const INTERACTABLE: CollisionLayers = CollisionGroup::of(&[Layer::Interactable]);
+    pub const DEBRIS: CollisionLayers = CollisionGroup::of(&[Layer::Debris]);
+
+    // Hazards are solid like the environment, but can also be queried on their own
+    pub const HAZARD: CollisionLayers = CollisionGroup::of(&[Layer::Environment, Layer::Hazard]);
+
+    // Breakables are solid until broken, attacks find them by their own layer
+    pub const BREAKABLE: CollisionLayers =
+        CollisionGroup::of(&[Layer::Environment, Layer::Breakable]);
+
+    // Pits and pools of acid, hazards that are fallen into rather than stood on
+    pub const HAZARD_VOLUME: CollisionLayers = CollisionGroup::of(&[Layer::Hazard]);
+
+    // One-way platforms are environment too, which side they're solid from is up to their collision hook
+    pub const ONE_WAY: CollisionLayers = CollisionGroup::of(&[Layer::Environment, Layer::OneWay]);
+
+    /// Collision layers for something on all of `layers`, colliding with whatever any of them
+    /// interacts with.
+    pub const fn of(layers: &[Layer]) -> CollisionLayers {
+        let mut memberships = NONE.0;
+        let mut i = 0;
+        while i < layers.len() {
+            memberships |= layers[i].mask().0;
+            i += 1;
+        }
+
+        CollisionLayers {
+            memberships: LayerMask(memberships),
+            filters: CollisionGroup::interactions(LayerMask(memberships)),
+        }
+    }
+
+    /// Every layer that collides with at least one of `layers`.
+    pub const fn interactions(layers: LayerMask) -> LayerMask {
+        let mut filters = NONE.0;
+        let mut i = 0;
+        while i < INTERACTIONS.len() {
+            let (a, b) = INTERACTIONS[i];
+            if layers.0 & a.mask().0 != 0 {
+                filters |= b.mask().0;
+            }
+            if layers.0 & b.mask().0 != 0 {
+                filters |= a.mask().0;
+            }
+            i += 1;
+        }
+
+        LayerMask(filters)
+    }
+
+    /// The hurtbox of a character in `faction`, it's only found by the attacks targeting it.
+    pub fn hurtbox(faction: Faction) -> CollisionLayers {
         CollisionLayers {
-            memberships: groups,
+            memberships: faction.layer(),
             filters: NONE,
         }
     }
@@ -38,4 +160,53 @@ impl CollisionGroup {
     pub fn filter(groups: LayerMask) -> SpatialQueryFilter {
         SpatialQueryFilter::from_mask(groups)
     }
+
+    /// A query for everything `faction` can hit.
+    pub fn targets(faction: Faction) -> SpatialQueryFilter {
+        CollisionGroup::filter(faction.targets())
+    }
+
+    /// A query for what a projectile aimed at `targets` runs into, its targets or a wall.
+    pub fn projectile(targets: LayerMask) -> SpatialQueryFilter {
+        CollisionGroup::filter(targets | CollisionGroup::interactions(Layer::Projectile.mask()))
+    }
+
+    /// The interaction matrix as a table, an `x` marking each pair of layers that collide.
+    pub fn dump() -> String {
+        let names: Vec<String> = Layer::ALL
+            .iter()
+            .map(|layer| format!("{layer:?}"))
+            .collect();
+        let width = names.iter().map(String::len).max().unwrap_or_default();
+
+        let header = format!("{:width$} {}", "", names.join(" "));
+        let rows = Layer::ALL.iter().zip(&names).map(|(row, row_name)| {
+            let filters = CollisionGroup::interactions(row.mask());
+            let cells: Vec<String> = Layer::ALL
+                .iter()
+                .zip(&names)
+                .map(|(column, column_name)| {
+                    let cell = if filters.0 & column.mask().0 != 0 {
+                        "x"
+                    } else {
+                        "."
+                    };
+                    let cell_width = column_name.len();
+                    format!("{cell:^cell_width$}")
+                })
+                .collect();
+
+            format!("{row_name:width$} {}", cells.join(" "))
+        });
+
+        std::iter::once(header)
+            .chain(rows)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Logs the interaction matrix, to check the layers against what's colliding in the debug render.
+pub fn log_collision_matrix() {
+    debug!("Collision layer matrix:\n{}", CollisionGroup::dump());
 }
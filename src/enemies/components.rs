@@ -1,4 +1,78 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
+use crate::input::inputs::Inputs;
+
 #[derive(Component)]
 pub struct Enemy;
+
+/// One step of an `InputScript`, what's held and for how long.
+pub struct ScriptStep {
+    duration: f32,
+    direction: Vec2,
+    held: Vec<Inputs>,
+}
+
+impl ScriptStep {
+    pub fn new(duration: f32, direction: Vec2, held: Vec<Inputs>) -> Self {
+        Self {
+            duration,
+            direction,
+            held,
+        }
+    }
+
+    /// Holds nothing for `duration` seconds.
+    pub fn wait(duration: f32) -> Self {
+        Self::new(duration, Vec2::ZERO, vec![])
+    }
+
+    pub fn direction(&self) -> Vec2 {
+        self.direction
+    }
+
+    pub fn held(&self) -> &[Inputs] {
+        &self.held
+    }
+}
+
+/// Fills a character's input buffer from a looping list of steps, standing in for the controls.
+#[derive(Component)]
+pub struct InputScript {
+    steps: Vec<ScriptStep>,
+    current: usize,
+    step_timer: Timer,
+}
+
+impl InputScript {
+    pub fn new(steps: Vec<ScriptStep>) -> Self {
+        let first = steps.first().map_or(0., |step| step.duration);
+
+        Self {
+            steps,
+            current: 0,
+            step_timer: Timer::from_seconds(first, TimerMode::Once),
+        }
+    }
+
+    /// Moves the script along by `delta`, returning the step to play this tick.
+    pub fn tick(&mut self, delta: Duration) -> Option<&ScriptStep> {
+        if self.step_timer.tick(delta).finished() {
+            // Loops back to the start once it runs out
+            self.current = if self.current + 1 < self.steps.len() {
+                self.current + 1
+            } else {
+                0
+            };
+
+            let duration = self
+                .steps
+                .get(self.current)
+                .map_or(0., |step| step.duration);
+            self.step_timer = Timer::from_seconds(duration, TimerMode::Once);
+        }
+
+        self.steps.get(self.current)
+    }
+}
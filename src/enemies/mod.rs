@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use self::systems::startup;
+use crate::system_sets::GameplaySet;
+
+use self::systems::{run_input_scripts, startup};
 
 mod components;
 mod resources;
@@ -10,6 +12,9 @@ pub struct EnemiesPlugin;
 
 impl Plugin for EnemiesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, startup);
+        app.add_systems(Startup, startup).add_systems(
+            FixedUpdate,
+            run_input_scripts.in_set(GameplaySet::InputCollect),
+        );
     }
 }
@@ -1,38 +1,186 @@
 use avian2d::prelude::*;
-use bevy::{
-    prelude::*,
-    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
-};
+use bevy::prelude::*;
 
-use crate::collision_groups::{CollisionGroup, ENEMY};
+use crate::{
+    behavior::{
+        crouch::Crouch,
+        demo_slash::{DemoSlash, Hitbox, SlashAttack},
+        jump::{Jump, JumpArc},
+        shot::{ProjectileType, Shot},
+        walk::Walk,
+        BehaviorInput,
+    },
+    characters::{
+        components::{Animator, Body, Faction},
+        resources::PlayerWalk,
+    },
+    collision_groups::CollisionGroup,
+    input::{buffer::InputBuffer, directions::InputDirection, inputs::Inputs},
+    state::{facing_direction::FacingDirection, grounded::Grounded, submerged::Submerged},
+    world::components::Carried,
+};
 
-use super::components::Enemy;
+use super::components::{Enemy, InputScript, ScriptStep};
 
 pub fn startup(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut projectile_types: ResMut<Assets<ProjectileType>>,
+    player_walk: Res<PlayerWalk>,
 ) {
+    let height = 100.;
+    let width = 50.;
+
+    let collider_ref = commands
+        .spawn((
+            SpatialBundle::default(),
+            CollisionGroup::COLLIDER,
+            Collider::rectangle(width, height),
+            Name::new("TestDummyCollider"),
+            Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+        ))
+        .id();
+
+    let hurtbox_ref = commands
+        .spawn((
+            SpatialBundle::default(),
+            Enemy,
+            Sensor,
+            CollisionGroup::hurtbox(Faction::Enemy),
+            Collider::rectangle(width, height),
+            Name::new("TestDummyHurtbox"),
+        ))
+        .id();
+
+    // Faces the player's spawn
+    let mut facing_direction = FacingDirection::new();
+    facing_direction.set(-1.);
+
+    // The dummy runs on the same behaviors as the player, its script stands in for the controls
     commands
         .spawn((
-            MaterialMesh2dBundle {
-                mesh: Mesh2dHandle(meshes.add(Rectangle::new(50., 100.0))),
-                material: materials.add(Color::srgb(1., 0., 0.)),
+            SpriteBundle {
+                sprite: Sprite {
+                    // Borrows the player's frames until it has its own, tinted to tell them apart
+                    color: Color::srgb(1., 0.3, 0.3),
+                    custom_size: Some(Vec2::new(width, height)),
+                    ..default()
+                },
                 transform: Transform::from_xyz(400., -305., 0.),
                 ..default()
             },
+            Animator::from_walk_cycle(&player_walk.frames()),
             Enemy,
-            CollisionGroup::COLLIDER,
-            Collider::rectangle(50., 100.),
-            Name::new("TestDummyCollider"),
+            Faction::Enemy,
+            Body {
+                height,
+                width,
+                collider_ref,
+            },
+            Grounded::new(50.),
+            Submerged::new(),
+            Carried::new(),
+            facing_direction,
+            InputBuffer::new(),
+            dummy_script(),
+        ))
+        .insert((
+            RigidBody::Dynamic,
+            GravityScale(1.),
+            Friction::new(0.),
+            Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+            LinearVelocity::default(),
+            LockedAxes::ROTATION_LOCKED,
+            Name::new("TestDummy"),
+        ))
+        .insert((
+            Crouch::new(),
+            Walk::new(4.3, 200., 3., 0.75, 1.2),
+            Jump::new(
+                JumpArc {
+                    apex_height: 100.,
+                    time_to_apex: 0.5,
+                    fall_multiplier: 1.5,
+                    apex_hang: 0.,
+                    apex_gravity_factor: 0.5,
+                    release_cut: 0.5,
+                    terminal_velocity: 1200.,
+                },
+                0,
+                0.1,
+                0.2,
+            ),
+            DemoSlash::new(
+                vec![SlashAttack::new(
+                    0.3,
+                    0.2,
+                    0.3,
+                    0.,
+                    1.,
+                    vec![Hitbox::new(Vec2::new(60., 0.), Vec2::new(50., 40.))],
+                )],
+                None,
+                None,
+                0.,
+            ),
+            Shot::new(
+                projectile_types.add(ProjectileType {
+                    name: "DummyBullet".to_string(),
+                    speed: 300.,
+                    gravity: 0.,
+                    lifetime: 2.,
+                    pierce: 0,
+                    bounces: 0,
+                    size: 15.,
+                    energy_cost: 0.,
+                }),
+                projectile_types.add(ProjectileType {
+                    name: "DummyChargedBullet".to_string(),
+                    speed: 400.,
+                    gravity: 0.3,
+                    lifetime: 3.,
+                    pierce: 0,
+                    bounces: 2,
+                    size: 25.,
+                    energy_cost: 0.,
+                }),
+                1.,
+            ),
+        ))
+        .insert((
+            BehaviorInput::<Crouch, InputDirection>::new(InputDirection::Down),
+            BehaviorInput::<Jump>::new(Inputs::Jump),
+            BehaviorInput::<DemoSlash>::new(Inputs::Primary),
+            BehaviorInput::<Shot>::new(Inputs::Secondary),
         ))
-        .with_children(|parent| {
-            parent.spawn((
-                SpatialBundle::default(),
-                Enemy,
-                CollisionGroup::hurtbox(ENEMY),
-                Collider::rectangle(50., 100.),
-                Name::new("TestDummyHurtbox"),
-            ));
-        });
+        .add_child(collider_ref)
+        .add_child(hurtbox_ref);
+}
+
+/// Paces back and forth, then jumps, slashes and shoots towards the player's spawn.
+fn dummy_script() -> InputScript {
+    InputScript::new(vec![
+        ScriptStep::wait(1.),
+        ScriptStep::new(0.4, Vec2::X, vec![]),
+        ScriptStep::new(0.4, Vec2::NEG_X, vec![]),
+        ScriptStep::wait(0.5),
+        ScriptStep::new(0.1, Vec2::ZERO, vec![Inputs::Jump]),
+        ScriptStep::wait(1.),
+        ScriptStep::new(0.1, Vec2::ZERO, vec![Inputs::Primary]),
+        ScriptStep::wait(0.8),
+        ScriptStep::new(0.1, Vec2::ZERO, vec![Inputs::Secondary]),
+    ])
+}
+
+/// Plays each script into its character's input buffer, in place of the controls.
+pub fn run_input_scripts(
+    mut q_script: Query<(&mut InputScript, &mut InputBuffer)>,
+    time: Res<Time>,
+) {
+    for (mut script, mut buffer) in q_script.iter_mut() {
+        let Some(step) = script.tick(time.delta()) else {
+            continue;
+        };
+
+        buffer.drive(step.held(), step.direction(), time.delta());
+    }
 }
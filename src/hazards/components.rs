@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// Spikes, pits, acid and anything else that hurts a character touching it and sends them back
+/// to the last safe ground they stood on.
+#[derive(Component)]
+pub struct Hazard {
+    damage: f32,
+}
+
+impl Hazard {
+    pub fn new(damage: f32) -> Self {
+        Self { damage }
+    }
+
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+}
+
+/// Where a character goes back to, after touching a hazard or after dying.
+#[derive(Component)]
+pub struct SafeGround {
+    // the last place the character stood for a moment without anything hurting it
+    last_safe: Vec2,
+    // where dying takes the character, the spawn point until checkpoints move it
+    checkpoint: Vec2,
+}
+
+impl SafeGround {
+    pub fn new(spawn: Vec2) -> Self {
+        Self {
+            last_safe: spawn,
+            checkpoint: spawn,
+        }
+    }
+
+    pub fn last_safe(&self) -> Vec2 {
+        self.last_safe
+    }
+
+    pub fn set_last_safe(&mut self, position: Vec2) {
+        self.last_safe = position;
+    }
+
+    pub fn checkpoint(&self) -> Vec2 {
+        self.checkpoint
+    }
+
+    pub fn set_checkpoint(&mut self, position: Vec2) {
+        self.checkpoint = position;
+    }
+}
+
+/// Why a character is being respawned, which decides where it ends up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RespawnKind {
+    /// Touched a hazard, back to the last safe ground with the damage taken.
+    Hazard,
+    /// Ran out of health, back to the checkpoint with full health.
+    Death,
+}
+
+/// Fades a character out, moves it back to safe ground and fades it back in, with its input
+/// locked the whole way through.
+#[derive(Component)]
+pub struct Respawn {
+    stage: Stage,
+    stage_timer: Timer,
+    // seconds each way, out and back in
+    fade_time: f32,
+}
+
+pub enum Stage {
+    Dormant,
+    FadingOut { kind: RespawnKind },
+    FadingIn,
+}
+
+impl Respawn {
+    pub fn new(fade_time: f32) -> Self {
+        Self {
+            stage: Stage::Dormant,
+            stage_timer: Timer::from_seconds(0., TimerMode::Once),
+            fade_time,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        !matches!(self.stage, Stage::Dormant)
+    }
+
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        if !matches!(stage, Stage::Dormant) {
+            self.stage_timer
+                .set_duration(Duration::from_secs_f32(self.fade_time));
+            self.stage_timer.reset();
+        }
+        self.stage = stage;
+    }
+
+    /// Ticks the current stage, returning whether it's finished.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.stage_timer.tick(delta).finished()
+    }
+
+    /// How dark the screen should be, from 0 to 1.
+    pub fn fade(&self) -> f32 {
+        match self.stage {
+            Stage::Dormant => 0.,
+            Stage::FadingOut { .. } => self.stage_timer.fraction(),
+            Stage::FadingIn => self.stage_timer.fraction_remaining(),
+        }
+    }
+}
+
+/// The full screen overlay respawns fade through.
+#[derive(Component)]
+pub struct FadeOverlay;
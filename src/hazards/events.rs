@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+/// Sent when a character touches a hazard, before it's respawned.
+#[derive(Event)]
+pub struct HazardHit {
+    pub entity: Entity,
+    pub hazard: Entity,
+    pub damage: f32,
+}
+
+/// Sent when a character runs out of health, it goes back to its checkpoint instead of safe ground.
+#[derive(Event)]
+pub struct Died {
+    pub entity: Entity,
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::{state::grounded::update_grounded, system_sets::GameplaySet};
+
+use self::{
+    events::{Died, HazardHit},
+    systems::{
+        fade_overlay, hurt_on_hazards, record_safe_ground, run_respawns, spawn_fade_overlay,
+    },
+};
+
+pub mod components;
+pub mod events;
+mod systems;
+
+pub struct HazardsPlugin;
+
+impl Plugin for HazardsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HazardHit>()
+            .add_event::<Died>()
+            .add_systems(Startup, spawn_fade_overlay)
+            .add_systems(
+                FixedUpdate,
+                (
+                    record_safe_ground
+                        .in_set(GameplaySet::StateUpdate)
+                        .after(update_grounded),
+                    (hurt_on_hazards, run_respawns)
+                        .chain()
+                        .in_set(GameplaySet::HitResolution),
+                ),
+            )
+            // The fade is only visual, so it follows along every frame
+            .add_systems(Update, fade_overlay);
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    characters::components::Body,
+    input::buffer::InputBuffer,
+    state::{grounded::Grounded, health::Health},
+    world::components::MovingPlatform,
+};
+
+use super::{
+    components::{FadeOverlay, Hazard, Respawn, RespawnKind, SafeGround, Stage},
+    events::{Died, HazardHit},
+};
+
+// how long a character has to stand somewhere before it counts as safe to come back to
+const SAFE_GROUND_TIME: Duration = Duration::from_millis(100);
+
+/// The first hazard `collider` is touching, if any.
+fn touching_hazard<'a>(
+    collisions: &Collisions,
+    collider: Entity,
+    q_hazard: &'a Query<&Hazard>,
+) -> Option<(Entity, &'a Hazard)> {
+    collisions
+        .collisions_with_entity(collider)
+        .map(|contacts| {
+            if contacts.entity1 == collider {
+                contacts.entity2
+            } else {
+                contacts.entity1
+            }
+        })
+        .find_map(|other| q_hazard.get(other).ok().map(|hazard| (other, hazard)))
+}
+
+/// Remembers where characters last stood safely, so hazards have somewhere to send them back to.
+///
+/// Moving platforms don't count, they won't be there anymore by the time we come back.
+pub fn record_safe_ground(
+    mut q_character: Query<(&mut SafeGround, &Grounded, &Body, &Transform, &Respawn)>,
+    q_hazard: Query<&Hazard>,
+    q_platform: Query<(), With<MovingPlatform>>,
+    collisions: Res<Collisions>,
+) {
+    for (mut safe_ground, grounded, body, transform, respawn) in q_character.iter_mut() {
+        if respawn.check() || grounded.time_since_landed() < SAFE_GROUND_TIME {
+            continue;
+        }
+
+        let Some(ground) = grounded.ground() else {
+            continue;
+        };
+
+        if q_platform.contains(ground.entity())
+            || touching_hazard(&collisions, body.collider_ref, &q_hazard).is_some()
+        {
+            continue;
+        }
+
+        safe_ground.set_last_safe(transform.translation.xy());
+    }
+}
+
+/// Damages characters touching a hazard and starts respawning them, at their checkpoint if
+/// that was the last of their health.
+pub fn hurt_on_hazards(
+    mut q_character: Query<
+        (Entity, &Body, &mut Health, &mut Respawn, &mut InputBuffer),
+        With<SafeGround>,
+    >,
+    q_hazard: Query<&Hazard>,
+    collisions: Res<Collisions>,
+    mut hits: EventWriter<HazardHit>,
+    mut deaths: EventWriter<Died>,
+) {
+    for (entity, body, mut health, mut respawn, mut buffer) in q_character.iter_mut() {
+        if respawn.check() {
+            continue;
+        }
+
+        let Some((hazard_entity, hazard)) =
+            touching_hazard(&collisions, body.collider_ref, &q_hazard)
+        else {
+            continue;
+        };
+
+        hits.send(HazardHit {
+            entity,
+            hazard: hazard_entity,
+            damage: hazard.damage(),
+        });
+
+        let kind = if health.damage(hazard.damage()) {
+            deaths.send(Died { entity });
+            RespawnKind::Death
+        } else {
+            RespawnKind::Hazard
+        };
+
+        buffer.block_all();
+        respawn.set_stage(Stage::FadingOut { kind });
+    }
+}
+
+/// Holds respawning characters in place while the screen fades, and moves them once it's dark.
+pub fn run_respawns(
+    mut q_character: Query<(
+        &mut Respawn,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut Health,
+        &SafeGround,
+        &mut InputBuffer,
+    )>,
+    time: Res<Time>,
+) {
+    for (mut respawn, mut transform, mut velocity, mut health, safe_ground, mut buffer) in
+        q_character.iter_mut()
+    {
+        let stage_finished = respawn.tick(time.delta());
+
+        match *respawn.stage() {
+            Stage::FadingOut { kind } if stage_finished => {
+                let target = match kind {
+                    RespawnKind::Hazard => safe_ground.last_safe(),
+                    RespawnKind::Death => {
+                        health.restore();
+                        safe_ground.checkpoint()
+                    }
+                };
+
+                transform.translation = target.extend(transform.translation.z);
+                velocity.0 = Vec2::ZERO;
+                respawn.set_stage(Stage::FadingIn);
+            }
+            Stage::FadingIn if stage_finished => {
+                buffer.clear_blocker();
+                respawn.set_stage(Stage::Dormant);
+            }
+            Stage::FadingOut { .. } | Stage::FadingIn => {
+                velocity.0 = Vec2::ZERO;
+            }
+            Stage::Dormant => {}
+        }
+    }
+}
+
+pub fn spawn_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        FadeOverlay,
+        Name::new("FadeOverlay"),
+    ));
+}
+
+/// Darkens the screen as far as the furthest along respawn.
+pub fn fade_overlay(
+    q_respawn: Query<&Respawn>,
+    mut q_overlay: Query<&mut BackgroundColor, With<FadeOverlay>>,
+) {
+    let fade = q_respawn.iter().map(Respawn::fade).fold(0., f32::max);
+
+    for mut color in q_overlay.iter_mut() {
+        color.0 = Color::BLACK.with_alpha(fade);
+    }
+}
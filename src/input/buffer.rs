@@ -3,17 +3,19 @@
 //! This module provides the `InputBuffer` component and related functionality
 //! for managing and querying game input states over time.
 
-use std::{collections::VecDeque, mem::discriminant};
+use std::{collections::VecDeque, mem::discriminant, time::Duration};
 
 use super::{
     blocker::{Blockable, Blocker},
     directions::InputDirection,
-    input_frame::{InputFrame, InputState},
+    input_frame::InputFrame,
     input_query::{InputLike, InputQuery},
     inputs::Inputs,
+    latch::InputLatch,
 };
 use bevy::prelude::*;
-use leafwing_input_manager::action_state::ActionState;
+
+use crate::player::components::Player;
 
 /// Component for managing a buffer of input frames and current input state.
 #[derive(Component)]
@@ -86,78 +88,60 @@ impl InputBuffer {
         self.blocker = Blocker::NONE;
     }
     
+    /// Feeds the buffer a tick of input from something other than the controls, like an enemy's
+    /// script. `held` are the actions held down this tick, edges are worked out from the last one.
+    pub fn drive(&mut self, held: &[Inputs], direction: Vec2, delta: Duration) {
+        let current = self.current_frame;
+        let mut frame = InputFrame::new();
+        frame.jump = current.jump.next(held.contains(&Inputs::Jump), delta);
+        frame.primary = current.primary.next(held.contains(&Inputs::Primary), delta);
+        frame.secondary = current.secondary.next(held.contains(&Inputs::Secondary), delta);
+        frame.special = current.special.next(held.contains(&Inputs::Special), delta);
+        frame.direction = InputDirection::from_raw(direction);
+        frame.dir_raw = direction;
+
+        self.push(frame);
+    }
+
+    /// Makes `frame` the current one, and buffers it if any action changed state.
+    fn push(&mut self, frame: InputFrame) {
+        let current = self.current_frame;
+        let changed = discriminant(&current.jump) != discriminant(&frame.jump)
+            || discriminant(&current.primary) != discriminant(&frame.primary)
+            || discriminant(&current.secondary) != discriminant(&frame.secondary)
+            || discriminant(&current.special) != discriminant(&frame.special);
+
+        self.current_frame = frame;
+
+        if changed {
+            self.add(frame);
+        }
+    }
+
     /// Checks if the given blockable input is currently blocked.
     pub fn blocked(&self, blockable: impl Blockable) -> bool {
         (self.blocker & blockable.to_blocker()).get() != 0
     }
 }
 
-/// System for updating input buffers based on raw input state.
-pub fn update_buffers(input_raw: Res<ActionState<Inputs>>, mut q_buffer: Query<&mut InputBuffer>) {
-    for mut buffer in q_buffer.iter_mut() {
-        let mut frame = InputFrame::new();
-        let mut changed = false;
-
-        for action in Inputs::all_actions() {
-            if matches!(action, Inputs::Directional | Inputs::Pause) {
-                continue;
-            }
-
-            let value = if input_raw.just_pressed(&action) {
-                InputState::JustPressed
-            } else if input_raw.pressed(&action) {
-                InputState::Pressed {
-                    duration: input_raw.current_duration(&action),
-                }
-            } else if input_raw.just_released(&action) {
-                InputState::JustReleased {
-                    duration: input_raw.previous_duration(&action),
-                }
-            } else {
-                InputState::Released
-            };
-
-            match action {
-                Inputs::Jump => {
-                    if discriminant(&buffer.current_frame.jump) != discriminant(&value) {
-                        changed = true
-                    };
-                    frame.jump = value
-                }
-                Inputs::Primary => {
-                    if discriminant(&buffer.current_frame.primary) != discriminant(&value) {
-                        changed = true
-                    };
-                    frame.primary = value
-                }
-                Inputs::Secondary => {
-                    if discriminant(&buffer.current_frame.secondary) != discriminant(&value) {
-                        changed = true
-                    };
-                    frame.secondary = value
-                }
-                Inputs::Special => {
-                    if discriminant(&buffer.current_frame.special) != discriminant(&value) {
-                        changed = true
-                    };
-                    frame.special = value
-                }
-                Inputs::Pause | Inputs::Directional => unreachable!(),
-            }
-        }
-
-        let move_axis = match input_raw.clamped_axis_pair(&Inputs::Directional) {
-            Some(data) => data.xy(),
-            None => continue,
-        };
-
-        frame.direction = InputDirection::from_raw(move_axis);
-        frame.dir_raw = move_axis;
+/// System for updating input buffers from the inputs latched since the last tick.
+/// Only the player is driven by the controls, other characters fill their own buffers.
+pub fn update_buffers(
+    mut latch: ResMut<InputLatch>,
+    mut q_buffer: Query<&mut InputBuffer, With<Player>>,
+) {
+    // Taken once per tick, whether or not there is anyone to hand it to
+    let mut frame = InputFrame::new();
+    frame.jump = latch.take(&Inputs::Jump);
+    frame.primary = latch.take(&Inputs::Primary);
+    frame.secondary = latch.take(&Inputs::Secondary);
+    frame.special = latch.take(&Inputs::Special);
+
+    let move_axis = latch.direction();
+    frame.direction = InputDirection::from_raw(move_axis);
+    frame.dir_raw = move_axis;
 
-        buffer.current_frame = frame;
-
-        if changed {
-            buffer.add(frame);
-        }
+    for mut buffer in q_buffer.iter_mut() {
+        buffer.push(frame);
     }
 }
@@ -115,7 +115,40 @@ impl InputDirection {
         }
         result
     }
-    
+
+    /// Returns this direction along with its two neighbours, from counter-clockwise to clockwise.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<InputDirection>` of three directions, or just `InputDirection::Neutral` for itself.
+    pub fn around(&self) -> Vec<InputDirection> {
+        if *self == InputDirection::Neutral {
+            return vec![InputDirection::Neutral];
+        }
+
+        let start = *self as u32 + 7;
+        (0..3).map(|i| Self::from_u32(start + i)).collect()
+    }
+
+    /// Converts the `InputDirection` to a unit vector pointing the same way.
+    ///
+    /// # Returns
+    ///
+    /// A normalized `Vec2`, or `Vec2::ZERO` for `InputDirection::Neutral`.
+    pub fn as_vec2(&self) -> Vec2 {
+        match self {
+            InputDirection::Up => Vec2::Y,
+            InputDirection::UpRight => Vec2::ONE.normalize(),
+            InputDirection::Right => Vec2::X,
+            InputDirection::DownRight => Vec2::new(1., -1.).normalize(),
+            InputDirection::Down => Vec2::NEG_Y,
+            InputDirection::DownLeft => Vec2::NEG_ONE.normalize(),
+            InputDirection::Left => Vec2::NEG_X,
+            InputDirection::UpLeft => Vec2::new(-1., 1.).normalize(),
+            InputDirection::Neutral => Vec2::ZERO,
+        }
+    }
+
     /// Converts a raw 2D input vector to an `InputDirection`.
     ///
     /// # Arguments
@@ -115,3 +115,19 @@ pub enum InputState {
     JustReleased { duration: Duration },
     Released,
 }
+
+impl InputState {
+    /// The state following this one a tick later, depending on whether the input is `held` now.
+    pub(super) fn next(self, held: bool, delta: Duration) -> Self {
+        match (self, held) {
+            (InputState::JustPressed, true) => InputState::Pressed { duration: delta },
+            (InputState::Pressed { duration }, true) => InputState::Pressed {
+                duration: duration + delta,
+            },
+            (_, true) => InputState::JustPressed,
+            (InputState::JustPressed, false) => InputState::JustReleased { duration: delta },
+            (InputState::Pressed { duration }, false) => InputState::JustReleased { duration },
+            (_, false) => InputState::Released,
+        }
+    }
+}
@@ -88,6 +88,8 @@ impl Inputs {
 
         input_map.insert(Self::Secondary, KeyCode::KeyB);
 
+        input_map.insert(Self::Special, KeyCode::KeyN);
+
         input_map
     }
 
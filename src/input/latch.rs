@@ -0,0 +1,102 @@
+//! Latching raw input between gameplay ticks.
+//!
+//! The action state updates once per rendered frame while gameplay runs at a fixed rate, so a
+//! frame can pass with no tick at all or run several. The `InputLatch` keeps every press and
+//! release seen since the last tick, and hands each of them to exactly one tick.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use leafwing_input_manager::action_state::ActionState;
+
+use super::{input_frame::InputState, inputs::Inputs};
+
+/// What happened to a button since the last tick.
+#[derive(Default, Clone, Copy)]
+struct ButtonLatch {
+    held: bool,
+    held_for: Duration,
+    // edges waiting for a tick to see them
+    pressed: bool,
+    released: Option<Duration>,
+}
+
+impl ButtonLatch {
+    fn record(&mut self, input_raw: &ActionState<Inputs>, action: &Inputs) {
+        if input_raw.just_released(action) {
+            self.released = Some(input_raw.previous_duration(action));
+        }
+        if input_raw.just_pressed(action) {
+            self.pressed = true;
+        }
+        self.held = input_raw.pressed(action);
+        self.held_for = input_raw.current_duration(action);
+    }
+
+    /// Hands the oldest edge not yet seen to a tick, or the held state when there is none.
+    fn take(&mut self) -> InputState {
+        if self.pressed {
+            self.pressed = false;
+            // Still held means any release came before the press, and is stale by now.
+            // Otherwise it was a tap between ticks, and the release goes to the next one.
+            if self.held {
+                self.released = None;
+            }
+            InputState::JustPressed
+        } else if self.held {
+            InputState::Pressed {
+                duration: self.held_for,
+            }
+        } else if let Some(duration) = self.released.take() {
+            InputState::JustReleased { duration }
+        } else {
+            InputState::Released
+        }
+    }
+}
+
+/// Raw input collected every frame, waiting to be turned into input frames on the next tick.
+#[derive(Resource, Default)]
+pub struct InputLatch {
+    jump: ButtonLatch,
+    primary: ButtonLatch,
+    secondary: ButtonLatch,
+    special: ButtonLatch,
+    direction: Vec2,
+}
+
+impl InputLatch {
+    fn button(&mut self, action: &Inputs) -> Option<&mut ButtonLatch> {
+        match action {
+            Inputs::Jump => Some(&mut self.jump),
+            Inputs::Primary => Some(&mut self.primary),
+            Inputs::Secondary => Some(&mut self.secondary),
+            Inputs::Special => Some(&mut self.special),
+            Inputs::Directional | Inputs::Pause => None,
+        }
+    }
+
+    /// Takes the state of `action` for this tick, consuming its pending edge.
+    pub fn take(&mut self, action: &Inputs) -> InputState {
+        self.button(action)
+            .map_or(InputState::Released, ButtonLatch::take)
+    }
+
+    /// The latest directional input.
+    pub fn direction(&self) -> Vec2 {
+        self.direction
+    }
+}
+
+/// Records the action state into the latch, once per rendered frame.
+pub fn record_inputs(input_raw: Res<ActionState<Inputs>>, mut latch: ResMut<InputLatch>) {
+    for action in Inputs::all_actions() {
+        if let Some(button) = latch.button(&action) {
+            button.record(&input_raw, &action);
+        }
+    }
+
+    if let Some(data) = input_raw.clamped_axis_pair(&Inputs::Directional) {
+        latch.direction = data.xy();
+    }
+}
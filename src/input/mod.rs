@@ -1,7 +1,13 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
-use self::{buffer::update_buffers, inputs::Inputs};
+use crate::system_sets::GameplaySet;
+
+use self::{
+    buffer::update_buffers,
+    inputs::Inputs,
+    latch::{record_inputs, InputLatch},
+};
 
 // use self::buffers::update_inputs;
 
@@ -10,6 +16,7 @@ pub mod directions;
 mod input_frame;
 pub mod input_query;
 pub mod inputs;
+mod latch;
 pub mod blocker;
 
 pub struct InputHandlerPlugin;
@@ -17,7 +24,10 @@ pub struct InputHandlerPlugin;
 impl Plugin for InputHandlerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(InputManagerPlugin::<Inputs>::default())
-            .add_systems(Update, update_buffers)
+            .init_resource::<InputLatch>()
+            // The action state only updates once per frame, so it's latched here for the ticks
+            .add_systems(PreUpdate, record_inputs.after(InputManagerSystem::Update))
+            .add_systems(FixedUpdate, update_buffers.in_set(GameplaySet::InputCollect))
             .init_resource::<ActionState<Inputs>>()
             .insert_resource(Inputs::input_map());
     }
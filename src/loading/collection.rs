@@ -0,0 +1,27 @@
+use std::any::type_name;
+
+use bevy::prelude::*;
+
+use super::resources::LoadingAssets;
+
+/// A resource holding handles that have to finish loading before play can start.
+pub trait AssetCollection: Resource + FromWorld {
+    fn handles(&self) -> Vec<UntypedHandle>;
+}
+
+pub trait LoadingAppExt {
+    /// Starts loading collection `T` and holds `GameState::Loading` until all of it has loaded.
+    fn load_collection<T: AssetCollection>(&mut self) -> &mut Self;
+}
+
+impl LoadingAppExt for App {
+    fn load_collection<T: AssetCollection>(&mut self) -> &mut Self {
+        self.init_resource::<T>();
+
+        let handles = self.world().resource::<T>().handles();
+        self.world_mut()
+            .get_resource_or_insert_with(LoadingAssets::default)
+            .register(type_name::<T>(), handles);
+        self
+    }
+}
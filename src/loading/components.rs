@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// The screen covering the world until it's done loading.
+#[derive(Component)]
+pub struct LoadingScreen;
+
+/// The loading progress, or what failed to load.
+#[derive(Component)]
+pub struct LoadingText;
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+
+use self::{
+    resources::LoadingAssets,
+    systems::{
+        despawn_loading_screen, pause_physics, resume_physics, show_failures, spawn_loading_screen,
+        track_loading,
+    },
+};
+
+pub mod collection;
+pub mod components;
+pub mod resources;
+mod systems;
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        // The world holds still until everything it needs has loaded
+        app.init_resource::<LoadingAssets>()
+            .add_systems(Startup, (pause_physics, spawn_loading_screen))
+            .add_systems(Update, track_loading.run_if(in_state(GameState::Loading)))
+            .add_systems(OnEnter(GameState::Failed), show_failures)
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (resume_physics, despawn_loading_screen),
+            );
+    }
+}
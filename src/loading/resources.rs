@@ -0,0 +1,64 @@
+use bevy::{asset::LoadState, prelude::*};
+
+/// Every handle registered through `load_collection`, and how far along loading them is.
+#[derive(Resource, Default)]
+pub struct LoadingAssets {
+    collections: Vec<(&'static str, Vec<UntypedHandle>)>,
+    loaded: usize,
+    failures: Vec<String>,
+}
+
+impl LoadingAssets {
+    pub fn register(&mut self, collection: &'static str, handles: Vec<UntypedHandle>) {
+        self.collections.push((collection, handles));
+    }
+
+    pub fn total(&self) -> usize {
+        self.collections
+            .iter()
+            .map(|(_, handles)| handles.len())
+            .sum()
+    }
+
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    /// Fraction of the registered assets that have loaded, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        match self.total() {
+            0 => 1.,
+            total => self.loaded as f32 / total as f32,
+        }
+    }
+
+    /// Describes every asset that failed to load, by collection and path.
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+
+    /// Polls the load state of every handle, returning true if the loaded count changed.
+    pub fn update(&mut self, asset_server: &AssetServer) -> bool {
+        let mut loaded = 0;
+        self.failures.clear();
+
+        for (collection, handles) in &self.collections {
+            for handle in handles {
+                match asset_server.get_load_state(handle.id()) {
+                    Some(LoadState::Loaded) => loaded += 1,
+                    Some(LoadState::Failed(_)) => {
+                        let path = handle
+                            .path()
+                            .map_or_else(|| format!("{:?}", handle.id()), ToString::to_string);
+                        self.failures.push(format!("{collection}: {path}"));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let changed = loaded != self.loaded;
+        self.loaded = loaded;
+        changed
+    }
+}
@@ -0,0 +1,100 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::GameState;
+
+use super::{
+    components::{LoadingScreen, LoadingText},
+    resources::LoadingAssets,
+};
+
+pub fn pause_physics(mut time: ResMut<Time<Physics>>) {
+    time.pause();
+}
+
+pub fn resume_physics(mut time: ResMut<Time<Physics>>) {
+    time.unpause();
+}
+
+pub fn spawn_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            LoadingScreen,
+            Name::new("LoadingScreen"),
+        ))
+        .with_children(|screen| {
+            screen.spawn((
+                TextBundle::from_section(
+                    "Loading 0%",
+                    TextStyle {
+                        font_size: 40.,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                LoadingText,
+            ));
+        });
+}
+
+pub fn despawn_loading_screen(
+    mut commands: Commands,
+    q_screen: Query<Entity, With<LoadingScreen>>,
+) {
+    for screen in q_screen.iter() {
+        commands.entity(screen).despawn_recursive();
+    }
+}
+
+/// Moves on to `Playing` once everything has loaded, or to `Failed` as soon as anything can't be.
+pub fn track_loading(
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<LoadingAssets>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut q_text: Query<&mut Text, With<LoadingText>>,
+) {
+    let changed = loading.update(&asset_server);
+
+    if !loading.failures().is_empty() {
+        for failure in loading.failures() {
+            error!("Failed to load {failure}");
+        }
+        next_state.set(GameState::Failed);
+        return;
+    }
+
+    if changed {
+        for mut text in q_text.iter_mut() {
+            if let Some(section) = text.sections.first_mut() {
+                section.value = format!("Loading {:.0}%", loading.progress() * 100.);
+            }
+        }
+    }
+
+    if loading.loaded() == loading.total() {
+        next_state.set(GameState::Playing);
+    }
+}
+
+/// Keeps the loading screen up, listing whatever failed to load.
+pub fn show_failures(loading: Res<LoadingAssets>, mut q_text: Query<&mut Text, With<LoadingText>>) {
+    for mut text in q_text.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value = format!("Failed to load:\n{}", loading.failures().join("\n"));
+            section.style.color = Color::srgb(1., 0.3, 0.3);
+        }
+    }
+}
@@ -1,17 +1,25 @@
+mod abilities;
 mod behavior;
+mod breakables;
 mod camera;
+mod characters;
 mod collision_groups;
 mod enemies;
+mod hazards;
 mod input;
+mod loading;
 mod macros;
+mod persistence;
 pub mod player;
 mod shape_intersections;
 mod state;
+mod system_sets;
+mod triggers;
 mod world;
 
 use avian2d::{
     debug_render::PhysicsDebugPlugin,
-    prelude::Gravity,
+    prelude::{Gravity, SubstepCount},
     schedule::{Physics, TimestepMode},
     PhysicsPlugins,
 };
@@ -25,15 +33,26 @@ use bevy::{
 };
 use std::time::Duration;
 
+use abilities::AbilitiesPlugin;
 use behavior::BehaviorPlugin;
+use breakables::BreakablesPlugin;
+use characters::CharactersPlugin;
+use collision_groups::log_collision_matrix;
 use enemies::EnemiesPlugin;
+use hazards::HazardsPlugin;
 use input::InputHandlerPlugin;
+use loading::LoadingPlugin;
+use persistence::PersistencePlugin;
 use player::PlayerPlugin;
 use state::StateHandlerPlugin;
+use system_sets::GameplaySetPlugin;
+use triggers::TriggersPlugin;
 use world::WorldPlugin;
 
 pub const WINDOW_WIDTH: f32 = 1920. * 0.75;
 pub const WINDOW_HEIGHT: f32 = 1080. * 0.75;
+/// How many gameplay ticks run per second, independent of the frame rate.
+pub const TICK_RATE: f64 = 64.;
 
 fn main() {
     App::new()
@@ -58,24 +77,35 @@ fn main() {
             .set(ImagePlugin::default_nearest()),))
         .init_state::<GameState>()
         .add_plugins((
-            PhysicsPlugins::default().with_length_unit(100.),
+            // Physics steps once per gameplay tick, right after it
+            PhysicsPlugins::new(FixedPostUpdate).with_length_unit(100.),
             PhysicsDebugPlugin::default(),
             // Project plugins
+            LoadingPlugin,
+            GameplaySetPlugin,
             InputHandlerPlugin,
             StateHandlerPlugin,
             WorldPlugin,
             PlayerPlugin,
             EnemiesPlugin,
             BehaviorPlugin,
+            PersistencePlugin,
+            AbilitiesPlugin,
+            CharactersPlugin,
+            (TriggersPlugin, HazardsPlugin, BreakablesPlugin),
         ))
+        .add_systems(Startup, log_collision_matrix)
         .insert_resource(Gravity(Vec2::NEG_Y * 1000.0))
+        .insert_resource(Time::<Fixed>::from_hz(TICK_RATE))
+        // Physics advances by exactly one tick each time it runs, so it can never drift from the
+        // gameplay that drives it. The substeps keep each integration step near a millisecond,
+        // which puts a full jump within half a pixel of its analytic apex
         .insert_resource(Time::new_with(Physics::from_timestep(
-            TimestepMode::Fixed {
-                delta: Duration::from_secs_f32(0.0001),
-                overstep: Duration::from_secs_f32(0.001),
-                max_delta_overstep: Duration::from_secs_f32(0.01),
+            TimestepMode::FixedOnce {
+                delta: Duration::from_secs_f64(1. / TICK_RATE),
             },
         )))
+        .insert_resource(SubstepCount(12))
         .run();
 }
 
@@ -84,11 +114,6 @@ enum GameState {
     #[default]
     Loading,
     Playing,
-}
-
-// This is the system use for loading assets in the test scene, 
-// it will be replaced with proper dynamic scene loading in the 
-// same dev cycle we implement level loading
-fn init_loader() {
-    
+    // Something couldn't be loaded, the failures are listed in `LoadingAssets`
+    Failed,
 }
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use self::{resources::PersistentState, systems::save_persistent_state};
+
+pub mod resources;
+mod systems;
+
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        // Loaded while building so startup systems can already read it
+        app.insert_resource(PersistentState::load()).add_systems(
+            Update,
+            save_persistent_state.run_if(resource_changed::<PersistentState>),
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use std::{collections::BTreeSet, fs, io, path::Path};
+
+use bevy::prelude::*;
+
+const SAVE_PATH: &str = "save/state.txt";
+
+/// Flags that outlive a play session, like unlocked abilities or opened secrets.
+///
+/// Stored as plain text, one flag per line.
+#[derive(Resource, Default)]
+pub struct PersistentState {
+    flags: BTreeSet<String>,
+}
+
+impl PersistentState {
+    /// Loads the saved state, starting fresh if there is none or it can't be read.
+    pub fn load() -> Self {
+        match fs::read_to_string(SAVE_PATH) {
+            Ok(contents) => Self {
+                flags: contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect(),
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(error) => {
+                warn!("Could not read save file {SAVE_PATH}, starting fresh: {error}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = Path::new(SAVE_PATH).parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents = self
+            .flags
+            .iter()
+            .map(|flag| format!("{flag}\n"))
+            .collect::<String>();
+
+        fs::write(SAVE_PATH, contents)
+    }
+
+    pub fn set(&mut self, flag: impl Into<String>) {
+        self.flags.insert(flag.into());
+    }
+
+    pub fn check(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+use super::resources::PersistentState;
+
+pub fn save_persistent_state(state: Res<PersistentState>) {
+    if let Err(error) = state.save() {
+        error!("Could not write save file: {error}");
+    }
+}
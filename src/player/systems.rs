@@ -1,24 +1,47 @@
 use avian2d::prelude::*;
-use bevy::{
-    prelude::*,
-    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
-};
+use bevy::prelude::*;
 
 use crate::{
+    abilities::components::{Abilities, Ability},
     behavior::{
-        crouch::Crouch, demo_slash::DemoSlash, jump::Jump, kick::Kick, shot::Shot, slide::Slide,
-        walk::Walk, BehaviorInput,
+        climb::Climb,
+        crawl::Crawl,
+        crouch::Crouch,
+        demo_slash::{DemoSlash, Hitbox, SlashAttack},
+        drop_through::DropThrough,
+        grapple::Grapple,
+        jump::{Jump, JumpArc},
+        kick::Kick,
+        ledge_grab::LedgeGrab,
+        shot::{ProjectileType, Shot},
+        slide::Slide,
+        swim::Swim,
+        walk::Walk,
+        BehaviorInput,
+    },
+    characters::{
+        components::{Animator, Body, Faction},
+        resources::PlayerWalk,
+    },
+    collision_groups::CollisionGroup,
+    hazards::components::{Respawn, SafeGround},
+    input::{buffer::InputBuffer, directions::InputDirection, inputs::Inputs},
+    persistence::resources::PersistentState,
+    state::{
+        energy::Energy, facing_direction::FacingDirection, grounded::Grounded, health::Health,
+        submerged::Submerged,
     },
-    collision_groups::{CollisionGroup, PLAYER},
-    input::{buffer::InputBuffer, inputs::Inputs}, state::{facing_direction::FacingDirection, grounded::Grounded},
+    triggers::components::TriggerTag,
+    world::components::Carried,
 };
 
 use super::components::*;
 
 pub fn startup(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut projectile_types: ResMut<Assets<ProjectileType>>,
+    persistent_state: Res<PersistentState>,
+    player_walk: Res<PlayerWalk>,
 ) {
     let height = 100.;
     let width = 50.;
@@ -37,7 +60,7 @@ pub fn startup(
         .spawn((
             SpatialBundle::default(),
             Sensor,
-            CollisionGroup::hurtbox(PLAYER),
+            CollisionGroup::hurtbox(Faction::Player),
             Collider::rectangle(width, height),
             Name::new("PlayerHurtbox"),
         ))
@@ -51,17 +74,39 @@ pub fn startup(
 
     commands
         .spawn((
-            MaterialMesh2dBundle {
-                mesh: Mesh2dHandle(meshes.add(Rectangle::new(50., 100.0))),
-                material: materials.add(Color::srgb(0., 0., 1.)),
+            SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(width, height)),
+                    ..default()
+                },
                 transform: Transform::from_translation(Vec3::ZERO),
                 ..default()
             },
+            Animator::from_walk_cycle(&player_walk.frames()),
             Player,
-            Grounded::new(),
+            Faction::Player,
+            Grounded::new(50.),
+            Submerged::new(),
+            Carried::new(),
+            TriggerTag("player"),
             player_body,
             FacingDirection::new(),
             InputBuffer::new(),
+            Energy::new(100., 25., 0.75, 10.),
+            Abilities::restore(
+                &[
+                    Ability::Slide,
+                    Ability::LedgeGrab,
+                    Ability::Climb,
+                    Ability::Swim,
+                ],
+                &persistent_state,
+            ),
+        ))
+        .insert((
+            Health::new(100.),
+            SafeGround::new(Vec2::ZERO),
+            Respawn::new(0.3),
         ))
         .insert((
             RigidBody::Dynamic,
@@ -75,13 +120,120 @@ pub fn startup(
         ))
         .insert((
             Crouch::new(),
-            Walk::new(4.3, 300., 3.),
-            BehaviorInput::<DemoSlash>::new(Inputs::Primary, DemoSlash::new()),
-            BehaviorInput::<Shot>::new(Inputs::Secondary, Shot::new()),
-            Slide::new(500.),
-            Jump::new(500.),
-            Kick::new(2200.),
+            Walk::new(4.3, 300., 3., 0.75, 1.2),
+            Crawl::new(4.3, 120., 3.),
+            DemoSlash::new(
+                slash_combo(),
+                Some(SlashAttack::new(
+                    0.1,
+                    0.25,
+                    0.15,
+                    0.,
+                    1.,
+                    vec![Hitbox::new(Vec2::new(10., 75.), Vec2::new(70., 50.))],
+                )),
+                Some(SlashAttack::new(
+                    0.05,
+                    0.25,
+                    0.1,
+                    0.,
+                    1.,
+                    vec![Hitbox::new(Vec2::new(0., -75.), Vec2::new(50., 60.))],
+                )),
+                0.4,
+            ),
+            Shot::new(
+                projectile_types.add(ProjectileType {
+                    name: "Bullet".to_string(),
+                    speed: 500.,
+                    gravity: 0.,
+                    lifetime: 2.,
+                    pierce: 0,
+                    bounces: 0,
+                    size: 15.,
+                    energy_cost: 5.,
+                }),
+                projectile_types.add(ProjectileType {
+                    name: "ChargedBullet".to_string(),
+                    speed: 800.,
+                    gravity: 0.,
+                    lifetime: 3.,
+                    pierce: 2,
+                    bounces: 1,
+                    size: 30.,
+                    energy_cost: 30.,
+                }),
+                0.8,
+            ),
+            Grapple::new(400., 40., 200., 600.),
+            Slide::new(500., 900., 15.),
+            Jump::new(
+                JumpArc {
+                    apex_height: 125.,
+                    time_to_apex: 0.5,
+                    fall_multiplier: 1.5,
+                    apex_hang: 0.05,
+                    apex_gravity_factor: 0.5,
+                    release_cut: 0.5,
+                    terminal_velocity: 1200.,
+                },
+                1,
+                0.1,
+                0.2,
+            ),
+            Kick::new(2200., 20.),
+            LedgeGrab::new(20.),
+            Climb::new(200.),
+            Swim::new(250., 4., 0.6),
+            DropThrough::new(0.25),
+        ))
+        .insert((
+            BehaviorInput::<Crouch, InputDirection>::new(InputDirection::Down),
+            BehaviorInput::<DemoSlash>::new(Inputs::Primary),
+            BehaviorInput::<Shot>::new(Inputs::Secondary),
+            BehaviorInput::<Grapple>::new(Inputs::Special),
+            BehaviorInput::<Slide>::new(Inputs::Jump),
+            BehaviorInput::<Jump>::new(Inputs::Jump),
+            BehaviorInput::<Kick>::new(Inputs::Jump),
+            BehaviorInput::<DropThrough>::new(Inputs::Jump),
+            BehaviorInput::<Climb, InputDirection>::new(InputDirection::Up),
+            BehaviorInput::<LedgeGrab, InputDirection>::new(InputDirection::Up),
         ))
         .add_child(collider_ref)
         .add_child(hurtbox_ref);
 }
+
+fn slash_combo() -> Vec<SlashAttack> {
+    let side = 25. / 2.25;
+
+    vec![
+        SlashAttack::new(
+            0.1,
+            0.3,
+            0.1,
+            0.,
+            1.,
+            vec![
+                Hitbox::new(Vec2::new(50., 25. + side), Vec2::splat(side)),
+                Hitbox::new(Vec2::new(50., -25. - side), Vec2::splat(side)),
+                Hitbox::new(Vec2::new(62.5, 0.), Vec2::splat(25.)),
+            ],
+        ),
+        SlashAttack::new(
+            0.08,
+            0.2,
+            0.15,
+            150.,
+            1.,
+            vec![Hitbox::new(Vec2::new(60., 0.), Vec2::new(50., 40.))],
+        ),
+        SlashAttack::new(
+            0.15,
+            0.3,
+            0.35,
+            300.,
+            3.,
+            vec![Hitbox::new(Vec2::new(65., 10.), Vec2::new(70., 80.))],
+        ),
+    ]
+}
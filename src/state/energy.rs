@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{characters::components::Faction, system_sets::GameplaySet};
+
+/// A meter that behaviors spend from, refilled over time and by landing hits.
+#[derive(Component)]
+pub struct Energy {
+    current: f32,
+    max: f32,
+    // energy per second once regeneration kicks in
+    regen_rate: f32,
+    // time after spending before regeneration starts again
+    regen_delay: Timer,
+    gain_on_hit: f32,
+}
+
+impl Energy {
+    pub fn new(max: f32, regen_rate: f32, regen_delay: f32, gain_on_hit: f32) -> Self {
+        let mut regen_delay = Timer::from_seconds(regen_delay, TimerMode::Once);
+        regen_delay.tick(regen_delay.duration());
+
+        Self {
+            current: max,
+            max,
+            regen_rate,
+            regen_delay,
+            gain_on_hit,
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        self.current
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// Checks if `energy` covers `cost`, characters without an energy meter can always afford it.
+    pub fn affords(energy: Option<&Self>, cost: f32) -> bool {
+        energy.map_or(true, |energy| energy.current >= cost)
+    }
+
+    /// Spends `cost`, restarting the regeneration delay. Check `affords` first.
+    pub fn spend(&mut self, cost: f32) {
+        if cost <= 0. {
+            return;
+        }
+        self.current = (self.current - cost).max(0.);
+        self.regen_delay.reset();
+    }
+
+    pub fn gain(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    /// Refunds an attack by `faction` that landed on something on `target_layers`. Only hits on a
+    /// character it fights count, not hazards or breakable walls.
+    pub fn gain_from_hit(
+        energy: Option<&mut Self>,
+        faction: Faction,
+        target_layers: Option<&CollisionLayers>,
+    ) {
+        let Some(energy) = energy else {
+            return;
+        };
+
+        if target_layers.is_some_and(|layers| faction.is_hostile(layers)) {
+            energy.gain(energy.gain_on_hit);
+        }
+    }
+
+    fn regenerate(&mut self, delta: Duration) {
+        if self.regen_delay.tick(delta).finished() {
+            self.gain(self.regen_rate * delta.as_secs_f32());
+        }
+    }
+}
+
+pub fn regenerate_energy(mut q_energy: Query<&mut Energy>, time: Res<Time>) {
+    for mut energy in q_energy.iter_mut() {
+        energy.regenerate(time.delta());
+    }
+}
+
+pub struct EnergyPlugin;
+
+impl Plugin for EnergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            regenerate_energy.in_set(GameplaySet::StateUpdate),
+        );
+    }
+}
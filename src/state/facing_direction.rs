@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     input::{buffer::InputBuffer, inputs::Inputs},
-    player::components::Player,
+    system_sets::GameplaySet,
 };
 
 #[derive(Component)]
@@ -22,9 +22,7 @@ impl FacingDirection {
     }
 }
 
-pub fn update_facing_direction_player(
-    mut q_player: Query<(&mut FacingDirection, &InputBuffer), With<Player>>,
-) {
+pub fn update_facing_direction_player(mut q_player: Query<(&mut FacingDirection, &InputBuffer)>) {
     for (mut direction, buffer) in q_player.iter_mut() {
         let x_input = buffer.this_frame().x();
 
@@ -38,6 +36,9 @@ pub struct FacingDirectionPlugin;
 
 impl Plugin for FacingDirectionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_facing_direction_player);
+        app.add_systems(
+            FixedUpdate,
+            update_facing_direction_player.in_set(GameplaySet::StateUpdate),
+        );
     }
 }
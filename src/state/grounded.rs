@@ -1,11 +1,79 @@
-use avian2d::collision::Collisions;
+use std::time::Duration;
+
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::{player::components::Body, GameState};
+use crate::{
+    characters::components::Body, system_sets::GameplaySet, world::components::SurfaceMaterial,
+};
+
+// how hard characters moving down a slope are pressed into it, so they follow it instead of running off
+const GROUND_STICK: f32 = 60.;
+// speed away from the ground past which we're leaving it, rather than just resting on it
+const SEPARATION_SPEED: f32 = 1.;
+
+/// What a grounded character is standing on, as of the last tick.
+#[derive(Clone, Copy, Debug)]
+pub struct Ground {
+    entity: Entity,
+    normal: Vec2,
+    slope: f32,
+    point: Vec2,
+    material: SurfaceMaterial,
+}
+
+impl Ground {
+    /// The collider the character is supported by, the flattest one when standing on several.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// The average of every walkable surface normal under the character, pointing away from the ground.
+    pub fn normal(&self) -> Vec2 {
+        self.normal
+    }
+
+    /// Angle between the ground normal and straight up, in radians.
+    pub fn slope(&self) -> f32 {
+        self.slope
+    }
+
+    /// World position of the deepest contact with the supporting entity.
+    pub fn point(&self) -> Vec2 {
+        self.point
+    }
+
+    pub fn material(&self) -> SurfaceMaterial {
+        self.material
+    }
+
+    /// Direction along the ground, pointing right.
+    pub fn tangent(&self) -> Vec2 {
+        Vec2::new(self.normal.y, -self.normal.x)
+    }
+
+    /// The velocity for moving along the ground at `speed`, positive being to the right.
+    /// Going downhill it also presses into the slope, so cresting it doesn't launch us off.
+    pub fn along(&self, speed: f32) -> Vec2 {
+        let velocity = self.tangent() * speed;
+
+        if velocity.y < 0. {
+            velocity - self.normal * GROUND_STICK
+        } else {
+            velocity
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct Grounded {
     in_state: bool,
+    ground: Option<Ground>,
+    // steepest surface that still counts as ground, in radians
+    max_slope: f32,
+    // how long the current state has been held, only one of these counts up at a time
+    airborne_time: Duration,
+    grounded_time: Duration,
 }
 
 impl Grounded {
@@ -25,28 +93,141 @@ impl Grounded {
         self.in_state
     }
 
-    pub fn new() -> Self {
-        Self { in_state: false }
+    /// The surface under the character, `None` while airborne.
+    pub fn ground(&self) -> Option<&Ground> {
+        self.ground.as_ref()
+    }
+
+    /// The ground under the character, unless `velocity` is taking it away from it, like on the
+    /// first frames of a jump while the contacts are still there.
+    pub fn footing(&self, velocity: Vec2) -> Option<&Ground> {
+        self.ground
+            .as_ref()
+            .filter(|ground| velocity.dot(ground.normal) <= SEPARATION_SPEED)
+    }
+
+    /// Steepest slope the character can stand on, in radians.
+    pub fn max_slope(&self) -> f32 {
+        self.max_slope
+    }
+
+    /// Time spent airborne since last leaving the ground, zero while grounded.
+    pub fn time_since_left_ground(&self) -> Duration {
+        self.airborne_time
+    }
+
+    /// Time spent on the ground since last landing, zero while airborne.
+    pub fn time_since_landed(&self) -> Duration {
+        self.grounded_time
+    }
+
+    /// Surfaces steeper than `max_slope_degrees` are treated as walls.
+    pub fn new(max_slope_degrees: f32) -> Self {
+        Self {
+            in_state: false,
+            ground: None,
+            max_slope: max_slope_degrees.to_radians(),
+            airborne_time: Duration::ZERO,
+            grounded_time: Duration::ZERO,
+        }
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        if self.in_state {
+            self.airborne_time = Duration::ZERO;
+            self.grounded_time += delta;
+        } else {
+            self.grounded_time = Duration::ZERO;
+            self.airborne_time += delta;
+        }
     }
 }
 
-pub fn update_grounded(mut q_player: Query<(&mut Grounded, &Body)>, collisions: Res<Collisions>) {
+pub fn update_grounded(
+    mut q_player: Query<(&mut Grounded, &Body)>,
+    q_collider: Query<(&Position, &Rotation)>,
+    q_material: Query<&SurfaceMaterial>,
+    collisions: Res<Collisions>,
+    time: Res<Time>,
+) {
     for (mut grounded, p_body) in q_player.iter_mut() {
+        let was_grounded = grounded.check();
         grounded.stop();
 
+        let Ok((position, rotation)) = q_collider.get(p_body.collider_ref) else {
+            continue;
+        };
+
+        let mut normal_sum = Vec2::ZERO;
+        // the flattest walkable surface wins, as (ground entity, slope, contact point)
+        let mut support: Option<(Entity, f32, Vec2)> = None;
+
         for collision in collisions.collisions_with_entity(p_body.collider_ref) {
-            for normal in collision.manifolds.iter().map(|manifold| {
-                if collision.entity1 == p_body.collider_ref {
-                    manifold.normal1
+            let is_first = collision.entity1 == p_body.collider_ref;
+            let other = if is_first {
+                collision.entity2
+            } else {
+                collision.entity1
+            };
+
+            for manifold in &collision.manifolds {
+                // The manifold normal points out of our collider, the ground's points the other way
+                let normal = if is_first {
+                    -manifold.normal1
                 } else {
-                    manifold.normal2
+                    -manifold.normal2
+                };
+                let slope = normal.angle_between(Vec2::Y).abs();
+
+                if normal.y <= 0. || slope > grounded.max_slope {
+                    continue;
                 }
-            }) {
-                if normal.y < 0. {
-                    grounded.start() // this already early returns
+
+                normal_sum += normal;
+
+                if support.is_some_and(|(_, flattest, _)| flattest <= slope) {
+                    continue;
                 }
+
+                let deepest = manifold
+                    .contacts
+                    .iter()
+                    .max_by(|a, b| a.penetration.total_cmp(&b.penetration));
+                let point = deepest.map_or(position.0, |contact| {
+                    let local = if is_first {
+                        contact.point1
+                    } else {
+                        contact.point2
+                    };
+                    position.0 + rotation.rotate(local)
+                });
+
+                support = Some((other, slope, point));
+            }
+        }
+
+        grounded.ground = support.map(|(entity, _, point)| {
+            let normal = normal_sum.normalize_or(Vec2::Y);
+
+            Ground {
+                entity,
+                normal,
+                slope: normal.angle_between(Vec2::Y).abs(),
+                point,
+                material: q_material.get(entity).copied().unwrap_or_default(),
             }
+        });
+        if grounded.ground.is_some() {
+            grounded.start();
         }
+
+        // A state change restarts the timers, so only count this frame if the state held
+        let delta = if was_grounded == grounded.check() {
+            time.delta()
+        } else {
+            Duration::ZERO
+        };
+        grounded.tick(delta);
     }
 }
 
@@ -54,6 +235,9 @@ pub struct GroundedPlugin;
 
 impl Plugin for GroundedPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_grounded.run_if(in_state(GameState::Playing)));
+        app.add_systems(
+            FixedUpdate,
+            update_grounded.in_set(GameplaySet::StateUpdate),
+        );
     }
 }
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+/// How much punishment a character can take before dying.
+#[derive(Component)]
+pub struct Health {
+    current: f32,
+    max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn get(&self) -> f32 {
+        self.current
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// Takes `amount` of damage, returning whether that was the last of it.
+    pub fn damage(&mut self, amount: f32) -> bool {
+        self.current = (self.current - amount).max(0.);
+        self.current <= 0.
+    }
+
+    /// Back to full, like after respawning from a death.
+    pub fn restore(&mut self) {
+        self.current = self.max;
+    }
+}
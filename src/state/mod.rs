@@ -1,14 +1,25 @@
 use bevy::prelude::*;
 
-use self::{facing_direction::FacingDirectionPlugin, grounded::GroundedPlugin};
+use self::{
+    energy::EnergyPlugin, facing_direction::FacingDirectionPlugin, grounded::GroundedPlugin,
+    submerged::SubmergedPlugin,
+};
 
+pub mod energy;
 pub mod facing_direction;
 pub mod grounded;
+pub mod health;
+pub mod submerged;
 
 pub struct StateHandlerPlugin;
 
 impl Plugin for StateHandlerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((FacingDirectionPlugin, GroundedPlugin));
+        app.add_plugins((
+            FacingDirectionPlugin,
+            GroundedPlugin,
+            SubmergedPlugin,
+            EnergyPlugin,
+        ));
     }
 }
@@ -0,0 +1,92 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    characters::components::Body,
+    collision_groups::{CollisionGroup, WATER},
+    system_sets::GameplaySet,
+    world::components::Water,
+};
+
+#[derive(Component)]
+pub struct Submerged {
+    water: Option<Water>,
+    surface: f32,
+    // fraction of the body below the surface, from 0 to 1
+    submersion: f32,
+}
+
+impl Submerged {
+    pub fn new() -> Self {
+        Self {
+            water: None,
+            surface: 0.,
+            submersion: 0.,
+        }
+    }
+
+    pub fn check(&self) -> bool {
+        self.water.is_some()
+    }
+
+    /// Returns the water the entity is currently in, if any.
+    pub fn water(&self) -> Option<&Water> {
+        self.water.as_ref()
+    }
+
+    pub fn surface(&self) -> f32 {
+        self.surface
+    }
+
+    pub fn submersion(&self) -> f32 {
+        self.submersion
+    }
+}
+
+/// Tracks which water volume an entity is in and how deep.
+/// Entities without a `Body` are treated as points.
+pub fn update_submerged(
+    mut q_submerged: Query<(&mut Submerged, &Transform, Option<&Body>)>,
+    q_water: Query<(&Transform, &Water)>,
+    spatial_query: SpatialQuery,
+) {
+    for (mut submerged, transform, o_body) in q_submerged.iter_mut() {
+        let position = transform.translation.xy();
+        let height = o_body.map_or(0., |body| body.height);
+
+        // Checking the feet lets characters start swimming as soon as they touch the water
+        let Some((water_transform, water)) = spatial_query
+            .point_intersections(
+                position - Vec2::new(0., height / 2.),
+                CollisionGroup::filter(WATER),
+            )
+            .into_iter()
+            .find_map(|entity| q_water.get(entity).ok())
+        else {
+            *submerged = Submerged::new();
+            continue;
+        };
+
+        let surface = water.surface(water_transform.translation.xy());
+        let bottom = position.y - height / 2.;
+
+        submerged.water = Some(*water);
+        submerged.surface = surface;
+        submerged.submersion = if height > 0. {
+            ((surface - bottom) / height).clamp(0., 1.)
+        } else {
+            1.
+        };
+    }
+}
+
+pub struct SubmergedPlugin;
+
+impl Plugin for SubmergedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            update_submerged.in_set(GameplaySet::StateUpdate),
+        );
+    }
+}
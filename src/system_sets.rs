@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+
+/// The stages of a gameplay tick, run in order every `FixedUpdate`.
+///
+/// Physics steps in `FixedPostUpdate`, so everything here sees the results of the previous step.
+/// Nothing runs until the game is done loading.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameplaySet {
+    /// Turns the raw action state into buffered input frames.
+    InputCollect,
+    /// Derives character state such as being grounded or submerged from the world.
+    StateUpdate,
+    /// Reads input and state, and decides what each character is doing.
+    Behaviors,
+    /// Applies forces and moves anything that isn't driven by a behavior.
+    Movement,
+    /// Resolves whatever the behaviors and movement ran into.
+    HitResolution,
+}
+
+/// The order behaviors run in within `GameplaySet::Behaviors`, so those sharing a character's
+/// velocity and input always see each other's results the same way.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BehaviorSet {
+    /// Walking, which everything after it is free to override.
+    Locomotion,
+    /// Swimming, climbing, hanging from ledges and grappling.
+    Traversal,
+    /// Dropping through platforms, crouching, sliding and crawling, in that order.
+    Stance,
+    /// Kicking, then jumping.
+    Airborne,
+    /// Slashing, then shooting.
+    Attacks,
+}
+
+pub struct GameplaySetPlugin;
+
+impl Plugin for GameplaySetPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            FixedUpdate,
+            (
+                GameplaySet::InputCollect,
+                GameplaySet::StateUpdate,
+                GameplaySet::Behaviors,
+                GameplaySet::Movement,
+                GameplaySet::HitResolution,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        )
+        .configure_sets(
+            FixedUpdate,
+            (
+                BehaviorSet::Locomotion,
+                BehaviorSet::Traversal,
+                BehaviorSet::Stance,
+                BehaviorSet::Airborne,
+                BehaviorSet::Attacks,
+            )
+                .chain()
+                .in_set(GameplaySet::Behaviors),
+        );
+    }
+}
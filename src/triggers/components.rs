@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+
+/// How often a trigger volume reports something entering it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TriggerMode {
+    /// Every time.
+    Always,
+    /// Only the first time, after that the volume goes quiet for good.
+    Once,
+    /// At most once every so many seconds, anything entering in between is ignored until it leaves.
+    Cooldown(f32),
+}
+
+/// A sensor volume that reports what enters, stays in and leaves it through trigger events.
+///
+/// Level logic reads the events and matches on `id`, so a new room transition or music cue
+/// only needs a volume, not its own system.
+#[derive(Component)]
+pub struct TriggerVolume {
+    id: &'static str,
+    // the layers this volume detects, characters are found by their movement collider
+    layers: LayerMask,
+    // only entities carrying this tag are detected, when set
+    tag: Option<&'static str>,
+    mode: TriggerMode,
+    // whoever's entry was reported, each of them gets an exit once they leave
+    occupants: HashSet<Entity>,
+    // inside too, but came in while the volume wasn't reporting, so they're never reported at all
+    ignored: HashSet<Entity>,
+    cooldown: Timer,
+    spent: bool,
+}
+
+impl TriggerVolume {
+    pub fn new(
+        id: &'static str,
+        layers: LayerMask,
+        tag: Option<&'static str>,
+        mode: TriggerMode,
+    ) -> Self {
+        let cooldown = match mode {
+            TriggerMode::Cooldown(seconds) => seconds,
+            TriggerMode::Always | TriggerMode::Once => 0.,
+        };
+
+        let mut cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+        // The first entry is never held back
+        cooldown.tick(cooldown.duration());
+
+        Self {
+            id,
+            layers,
+            tag,
+            mode,
+            occupants: HashSet::new(),
+            ignored: HashSet::new(),
+            cooldown,
+            spent: false,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn layers(&self) -> LayerMask {
+        self.layers
+    }
+
+    /// Whether an entity tagged with `tag` is one this volume looks out for.
+    pub fn accepts(&self, tag: Option<&TriggerTag>) -> bool {
+        self.tag
+            .map_or(true, |wanted| tag.is_some_and(|tag| tag.0 == wanted))
+    }
+
+    /// A `Once` volume is spent after its first entry and won't report anything else, apart from
+    /// that entity leaving.
+    pub fn is_spent(&self) -> bool {
+        self.spent
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.cooldown.tick(delta);
+    }
+
+    /// Checks who is inside this tick against the last, returning who just came in and which
+    /// occupants just left. Those coming in aren't occupants until their entry is reported.
+    pub fn update_occupants(&mut self, inside: &HashSet<Entity>) -> (Vec<Entity>, Vec<Entity>) {
+        let exited = self.occupants.difference(inside).copied().collect();
+        self.occupants.retain(|entity| inside.contains(entity));
+        self.ignored.retain(|entity| inside.contains(entity));
+
+        let entered = inside
+            .iter()
+            .filter(|entity| !self.occupants.contains(*entity) && !self.ignored.contains(*entity))
+            .copied()
+            .collect();
+
+        (entered, exited)
+    }
+
+    pub fn occupants(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.occupants.iter().copied()
+    }
+
+    /// Whether `entity` coming in should be reported, starting the cooldown or spending the volume
+    /// if so. Only then does it become an occupant, otherwise it's ignored until it leaves.
+    pub fn report_entry(&mut self, entity: Entity) -> bool {
+        if self.spent || !self.cooldown.finished() {
+            self.ignored.insert(entity);
+            return false;
+        }
+
+        self.occupants.insert(entity);
+
+        match self.mode {
+            TriggerMode::Always => {}
+            TriggerMode::Once => self.spent = true,
+            TriggerMode::Cooldown(_) => self.cooldown.reset(),
+        }
+        true
+    }
+}
+
+/// A label trigger volumes can filter on, like "player" for volumes only the player sets off.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TriggerTag(pub &'static str);
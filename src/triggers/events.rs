@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+/// Sent the first tick something is inside a trigger volume.
+#[derive(Event)]
+pub struct TriggerEntered {
+    pub trigger: Entity,
+    pub id: &'static str,
+    pub entity: Entity,
+}
+
+/// Sent every tick after entering that something is still inside a trigger volume.
+#[derive(Event)]
+pub struct TriggerStayed {
+    pub trigger: Entity,
+    pub id: &'static str,
+    pub entity: Entity,
+}
+
+/// Sent the first tick something is no longer inside a trigger volume, or has despawned.
+#[derive(Event)]
+pub struct TriggerExited {
+    pub trigger: Entity,
+    pub id: &'static str,
+    pub entity: Entity,
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::system_sets::GameplaySet;
+
+use self::{
+    events::{TriggerEntered, TriggerExited, TriggerStayed},
+    systems::update_triggers,
+};
+
+pub mod components;
+pub mod events;
+mod systems;
+
+pub struct TriggersPlugin;
+
+impl Plugin for TriggersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TriggerEntered>()
+            .add_event::<TriggerStayed>()
+            .add_event::<TriggerExited>()
+            .add_systems(
+                FixedUpdate,
+                update_triggers.in_set(GameplaySet::HitResolution),
+            );
+    }
+}
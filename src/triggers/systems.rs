@@ -0,0 +1,71 @@
+use avian2d::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::collision_groups::CollisionGroup;
+
+use super::{
+    components::{TriggerTag, TriggerVolume},
+    events::{TriggerEntered, TriggerExited, TriggerStayed},
+};
+
+/// Finds what's inside each trigger volume and reports what changed since the last tick.
+pub fn update_triggers(
+    mut q_trigger: Query<(Entity, &mut TriggerVolume, &Collider, &Transform)>,
+    q_parent: Query<&Parent>,
+    q_tag: Query<&TriggerTag>,
+    mut entered: EventWriter<TriggerEntered>,
+    mut stayed: EventWriter<TriggerStayed>,
+    mut exited: EventWriter<TriggerExited>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    for (trigger, mut volume, collider, transform) in q_trigger.iter_mut() {
+        volume.tick(time.delta());
+        // A spent volume still has to see its last occupants out
+        if volume.is_spent() && volume.occupants().next().is_none() {
+            continue;
+        }
+
+        // Character colliders are children of the character, so it's the character that gets reported
+        let inside: HashSet<Entity> = spatial_query
+            .shape_intersections(
+                collider,
+                transform.translation.xy(),
+                0.,
+                CollisionGroup::filter(volume.layers()),
+            )
+            .into_iter()
+            .map(|hit| q_parent.get(hit).map_or(hit, Parent::get))
+            .filter(|entity| volume.accepts(q_tag.get(*entity).ok()))
+            .collect();
+
+        let (new, gone) = volume.update_occupants(&inside);
+        let id = volume.id();
+
+        for entity in gone {
+            exited.send(TriggerExited {
+                trigger,
+                id,
+                entity,
+            });
+        }
+
+        for entity in volume.occupants() {
+            stayed.send(TriggerStayed {
+                trigger,
+                id,
+                entity,
+            });
+        }
+
+        for entity in new {
+            if volume.report_entry(entity) {
+                entered.send(TriggerEntered {
+                    trigger,
+                    id,
+                    entity,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashSet};
+
+/// A volume that can be climbed along its vertical axis, like a ladder or vines.
+#[derive(Component)]
+pub struct Climbable {
+    height: f32,
+}
+
+impl Climbable {
+    pub fn new(height: f32) -> Self {
+        Self { height }
+    }
+
+    /// Returns the world space y of the top and bottom ends, given the volume's center.
+    pub fn ends(&self, center: Vec2) -> (f32, f32) {
+        (center.y + self.height / 2., center.y - self.height / 2.)
+    }
+}
+
+/// A body of water that slows anything inside it and pushes characters up towards the surface.
+#[derive(Component, Clone, Copy)]
+pub struct Water {
+    height: f32,
+    buoyancy: f32,
+    drag: f32,
+    projectile_speed: f32,
+}
+
+impl Water {
+    /// `buoyancy` is relative to gravity, so anything above 1 floats.
+    /// `projectile_speed` is the fraction of their speed projectiles keep underwater.
+    pub fn new(height: f32, buoyancy: f32, drag: f32, projectile_speed: f32) -> Self {
+        Self {
+            height,
+            buoyancy,
+            drag,
+            projectile_speed,
+        }
+    }
+
+    /// Returns the world space y of the surface, given the volume's center.
+    pub fn surface(&self, center: Vec2) -> f32 {
+        center.y + self.height / 2.
+    }
+
+    pub fn buoyancy(&self) -> f32 {
+        self.buoyancy
+    }
+
+    pub fn drag(&self) -> f32 {
+        self.drag
+    }
+
+    pub fn projectile_speed(&self) -> f32 {
+        self.projectile_speed
+    }
+}
+
+/// A point the grappling hook can attach to even when it isn't part of the environment.
+#[derive(Component)]
+pub struct GrapplePoint;
+
+/// What a surface is made of, for anything that reacts to the ground underfoot.
+/// Surfaces without one are treated as `Stone`.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SurfaceMaterial {
+    #[default]
+    Stone,
+    Wood,
+    Metal,
+    Ice,
+}
+
+/// A platform that can be stood on from above, but passed through from below or the sides.
+#[derive(Component, Default)]
+pub struct OneWayPlatform {
+    // colliders partway through the platform, ignored until they're fully clear of it
+    passing: HashSet<Entity>,
+}
+
+impl OneWayPlatform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_passing(&self, collider: Entity) -> bool {
+        self.passing.contains(&collider)
+    }
+
+    pub fn start_passing(&mut self, collider: Entity) {
+        self.passing.insert(collider);
+    }
+
+    pub fn stop_passing(&mut self, collider: Entity) {
+        self.passing.remove(&collider);
+    }
+}
+
+/// How a moving platform carries on once it reaches the end of its path.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathMode {
+    /// Stops at the last waypoint.
+    Once,
+    /// Turns around at either end.
+    PingPong,
+    /// Goes from the last waypoint straight back to the first.
+    Loop,
+}
+
+/// How a moving platform speeds up and slows down between two waypoints.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Easing {
+    Linear,
+    /// Starts and stops gently, moving fastest halfway between waypoints.
+    InOut,
+}
+
+impl Easing {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::InOut => t * t * (3. - 2. * t),
+        }
+    }
+}
+
+/// A kinematic platform that follows a path of waypoints and can spin as it goes.
+///
+/// The platform is moved through its velocity rather than its transform, so the physics step
+/// knows how fast it's going and anything standing on it can be carried along.
+#[derive(Component)]
+pub struct MovingPlatform {
+    waypoints: Vec<Vec2>,
+    mode: PathMode,
+    easing: Easing,
+    speed: f32,
+    angular_velocity: f32,
+    // the waypoint we're leaving, and which way along the path we're headed
+    from: usize,
+    forward: bool,
+    // how far we are towards the next waypoint, from 0 to 1
+    progress: f32,
+    // time to wait at each waypoint before moving on
+    wait_timer: Timer,
+}
+
+impl MovingPlatform {
+    /// `speed` is the average speed between waypoints, easing changes how it's spread out.
+    /// `wait` is in seconds, and `angular_velocity` in radians per second.
+    pub fn new(
+        waypoints: Vec<Vec2>,
+        mode: PathMode,
+        easing: Easing,
+        speed: f32,
+        wait: f32,
+        angular_velocity: f32,
+    ) -> Self {
+        Self {
+            waypoints,
+            mode,
+            easing,
+            speed,
+            angular_velocity,
+            from: 0,
+            forward: true,
+            progress: 0.,
+            wait_timer: Timer::from_seconds(wait, TimerMode::Once),
+        }
+    }
+
+    /// Where the platform starts, its first waypoint.
+    pub fn start(&self) -> Vec2 {
+        self.waypoints.first().copied().unwrap_or_default()
+    }
+
+    pub fn angular_velocity(&self) -> f32 {
+        self.angular_velocity
+    }
+
+    fn next(&self) -> Option<usize> {
+        if self.waypoints.len() < 2 {
+            return None;
+        }
+
+        if self.forward {
+            match self.mode {
+                _ if self.from + 1 < self.waypoints.len() => Some(self.from + 1),
+                PathMode::Loop => Some(0),
+                PathMode::Once | PathMode::PingPong => None,
+            }
+        } else {
+            self.from.checked_sub(1)
+        }
+    }
+
+    /// Steps along the path and returns where the platform should be at the end of this tick.
+    pub fn advance(&mut self, delta: Duration) -> Vec2 {
+        let from = self.waypoints.get(self.from).copied().unwrap_or_default();
+
+        if !self.wait_timer.tick(delta).finished() {
+            return from;
+        }
+        let Some(next) = self.next() else {
+            return from;
+        };
+        let to = self.waypoints[next];
+
+        let length = from.distance(to).max(f32::EPSILON);
+        self.progress = (self.progress + self.speed * delta.as_secs_f32() / length).min(1.);
+        let position = from.lerp(to, self.easing.apply(self.progress));
+
+        if self.progress >= 1. {
+            self.from = next;
+            self.progress = 0.;
+            self.wait_timer.reset();
+
+            let at_end = if self.forward {
+                next + 1 == self.waypoints.len()
+            } else {
+                next == 0
+            };
+            if self.mode == PathMode::PingPong && at_end {
+                self.forward = !self.forward;
+            }
+        }
+
+        position
+    }
+}
+
+/// The velocity a character is picking up from the moving platform it stands on.
+///
+/// It's added on top of the character's own velocity for the physics step, and taken back off
+/// afterwards so behaviors only ever see how fast the character moves relative to the platform.
+///
+/// The carry is off from `GameplaySet::StateUpdate` until it's added back at the end of
+/// `GameplaySet::Movement`. Anything that reads or scales `LinearVelocity` has to run inside
+/// that window, or it will also act on the platform's motion.
+#[derive(Component, Default)]
+pub struct Carried {
+    velocity: Vec2,
+}
+
+impl Carried {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    pub fn set(&mut self, velocity: Vec2) {
+        self.velocity = velocity;
+    }
+}
@@ -1,10 +1,20 @@
 use avian2d::prelude::*;
 use bevy::{
+    color::palettes::css,
     prelude::*,
     sprite::{MaterialMesh2dBundle, Mesh2dHandle},
 };
 
-use crate::collision_groups::CollisionGroup;
+use crate::{
+    abilities::components::{Ability, AbilityPickup},
+    breakables::components::Breakable,
+    collision_groups::CollisionGroup,
+    hazards::components::Hazard,
+    persistence::resources::PersistentState,
+    triggers::components::TriggerVolume,
+};
+
+use super::components::{Climbable, GrapplePoint, MovingPlatform, OneWayPlatform, Water};
 
 pub fn spawn_cube(
     commands: &mut Commands,
@@ -14,18 +24,285 @@ pub fn spawn_cube(
     location: Vec2,
     size: Vec2,
     name: Name,
+) -> Entity {
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+                material: materials.add(color),
+                transform: Transform::from_translation(location.extend(0.)),
+                ..default()
+            },
+            CollisionGroup::ENVIRONMENT,
+            RigidBody::Static,
+            Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+            Collider::rectangle(size.x, size.y),
+            name,
+        ))
+        .id()
+}
+
+/// A block tilted by `angle` degrees, its top face makes a slope to walk on.
+pub fn spawn_slope(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    size: Vec2,
+    angle: f32,
+    name: Name,
 ) {
     commands.spawn((
         MaterialMesh2dBundle {
             mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
-            material: materials.add(color),
+            material: materials.add(Color::from(css::GRAY)),
+            transform: Transform::from_translation(location.extend(0.))
+                .with_rotation(Quat::from_rotation_z(angle.to_radians())),
+            ..default()
+        },
+        CollisionGroup::ENVIRONMENT,
+        RigidBody::Static,
+        Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+        Collider::rectangle(size.x, size.y),
+        name,
+    ));
+}
+
+pub fn spawn_one_way_platform(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    size: Vec2,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(Color::srgb(0.6, 0.6, 0.7)),
             transform: Transform::from_translation(location.extend(0.)),
             ..default()
         },
+        CollisionGroup::ONE_WAY,
+        RigidBody::Static,
+        Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+        Collider::rectangle(size.x, size.y),
+        OneWayPlatform::new(),
+        name,
+    ));
+}
+
+pub fn spawn_moving_platform(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    size: Vec2,
+    platform: MovingPlatform,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(Color::srgb(0.5, 0.5, 0.8)),
+            transform: Transform::from_translation(platform.start().extend(0.)),
+            ..default()
+        },
         CollisionGroup::ENVIRONMENT,
+        RigidBody::Kinematic,
+        LinearVelocity::default(),
+        AngularVelocity::default(),
+        Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+        Collider::rectangle(size.x, size.y),
+        platform,
+        name,
+    ));
+}
+
+pub fn spawn_ladder(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    color: Color,
+    location: Vec2,
+    size: Vec2,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(color),
+            // Sits behind characters so they stay visible while climbing
+            transform: Transform::from_translation(location.extend(-1.)),
+            ..default()
+        },
+        CollisionGroup::CLIMBABLE,
+        Sensor,
+        Collider::rectangle(size.x, size.y),
+        Climbable::new(size.y),
+        name,
+    ));
+}
+
+pub fn spawn_water(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    size: Vec2,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(Color::srgba(0.2, 0.4, 1., 0.4)),
+            // In front of characters so they read as submerged
+            transform: Transform::from_translation(location.extend(1.)),
+            ..default()
+        },
+        CollisionGroup::WATER,
+        Sensor,
+        Collider::rectangle(size.x, size.y),
+        Water::new(size.y, 1.2, 2.5, 0.4),
+        name,
+    ));
+}
+
+pub fn spawn_grapple_point(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    radius: f32,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(radius))),
+            material: materials.add(Color::srgb(1., 0.8, 0.)),
+            transform: Transform::from_translation(location.extend(0.)),
+            ..default()
+        },
+        CollisionGroup::GRAPPLE,
+        Sensor,
+        Collider::circle(radius),
+        GrapplePoint,
+        name,
+    ));
+}
+
+pub fn spawn_spikes(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    size: Vec2,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(Color::srgb(0.8, 0.1, 0.1)),
+            transform: Transform::from_translation(location.extend(0.)),
+            ..default()
+        },
+        CollisionGroup::HAZARD,
         RigidBody::Static,
         Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
         Collider::rectangle(size.x, size.y),
+        Hazard::new(20.),
+        name,
+    ));
+}
+
+pub fn spawn_acid(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    size: Vec2,
+    name: Name,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(Color::srgba(0.4, 1., 0.1, 0.7)),
+            transform: Transform::from_translation(location.extend(1.)),
+            ..default()
+        },
+        CollisionGroup::HAZARD_VOLUME,
+        Sensor,
+        Collider::rectangle(size.x, size.y),
+        Hazard::new(35.),
         name,
     ));
 }
+
+/// Spawns a breakable wall, unless it was already broken in an earlier session.
+pub fn spawn_breakable(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    breakable: Breakable,
+    persistent_state: &PersistentState,
+    name: Name,
+) {
+    if persistent_state.check(&breakable.flag()) {
+        return;
+    }
+
+    let size = breakable.size();
+
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Rectangle::new(size.x, size.y))),
+            material: materials.add(Color::srgb(0.45, 0.4, 0.35)),
+            transform: Transform::from_translation(location.extend(0.)),
+            ..default()
+        },
+        CollisionGroup::BREAKABLE,
+        RigidBody::Static,
+        Collider::rectangle(size.x, size.y),
+        breakable,
+        name,
+    ));
+}
+
+pub fn spawn_trigger(
+    commands: &mut Commands,
+    location: Vec2,
+    size: Vec2,
+    volume: TriggerVolume,
+    name: Name,
+) {
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(location.extend(0.))),
+        CollisionGroup::TRIGGER,
+        Sensor,
+        Collider::rectangle(size.x, size.y),
+        volume,
+        name,
+    ));
+}
+
+pub fn spawn_ability_pickup(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    location: Vec2,
+    ability: Ability,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(15.))),
+            material: materials.add(Color::srgb(0.3, 1., 0.3)),
+            transform: Transform::from_translation(location.extend(0.)),
+            ..default()
+        },
+        CollisionGroup::INTERACTABLE,
+        Sensor,
+        Collider::circle(15.),
+        AbilityPickup(ability),
+        Name::new(format!("{ability:?}Pickup")),
+    ));
+}
@@ -1,14 +1,35 @@
+use avian2d::schedule::PostProcessCollisions;
 use bevy::prelude::*;
 
+use crate::{state::grounded::update_grounded, system_sets::GameplaySet};
+
+pub mod components;
 mod functions;
+pub mod resources;
 mod systems;
 
+use resources::RoomBounds;
 use systems::*;
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, startup);
+        app.init_resource::<RoomBounds>()
+            .add_systems(Startup, startup)
+            .add_systems(
+                FixedUpdate,
+                (
+                    remove_carried_velocity
+                        .in_set(GameplaySet::StateUpdate)
+                        .after(update_grounded),
+                    // Water drag has to act before the carry is added back, so it only slows
+                    // a character relative to the platform it's standing on
+                    (apply_water_forces, move_platforms, apply_carried_velocity)
+                        .chain()
+                        .in_set(GameplaySet::Movement),
+                ),
+            )
+            .add_systems(PostProcessCollisions, one_way_platforms);
     }
 }
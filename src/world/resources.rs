@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::{WINDOW_HEIGHT, WINDOW_WIDTH};
+
+/// The extents of the current room, anything that leaves it is gone for good.
+#[derive(Resource)]
+pub struct RoomBounds {
+    rect: Rect,
+}
+
+impl RoomBounds {
+    pub fn new(rect: Rect) -> Self {
+        Self { rect }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.rect.contains(point)
+    }
+}
+
+impl Default for RoomBounds {
+    // The demo room is built to fill the window, centered on the origin
+    fn default() -> Self {
+        Self::new(Rect::from_center_size(
+            Vec2::ZERO,
+            Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT),
+        ))
+    }
+}
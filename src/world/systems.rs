@@ -1,14 +1,33 @@
-use bevy::prelude::*;
+use avian2d::prelude::*;
 use bevy::color::palettes::css;
+use bevy::{prelude::*, utils::HashSet};
 
-use crate::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::{
+    abilities::components::Ability,
+    behavior::{drop_through::DropThrough, AttackKind},
+    breakables::components::Breakable,
+    characters::components::Body,
+    collision_groups::{COLLIDER, PROJECTILE},
+    persistence::resources::PersistentState,
+    state::{grounded::Grounded, submerged::Submerged},
+    triggers::components::{TriggerMode, TriggerVolume},
+    WINDOW_HEIGHT, WINDOW_WIDTH,
+};
 
-use super::functions::spawn_cube;
+use super::{
+    components::{Carried, Easing, MovingPlatform, OneWayPlatform, PathMode, SurfaceMaterial},
+    functions::{
+        spawn_ability_pickup, spawn_acid, spawn_breakable, spawn_cube, spawn_grapple_point,
+        spawn_ladder, spawn_moving_platform, spawn_one_way_platform, spawn_slope, spawn_spikes,
+        spawn_trigger, spawn_water,
+    },
+};
 
 pub fn startup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    persistent_state: Res<PersistentState>,
 ) {
     commands.spawn(Camera2dBundle::default());
 
@@ -27,7 +46,7 @@ pub fn startup(
         },
         Name::new("WorldGround"),
     );
-    
+
     spawn_cube(
         &mut commands,
         &mut meshes,
@@ -58,7 +77,7 @@ pub fn startup(
         },
         Name::new("WorldOuterWallLeft"),
     );
-    
+
     spawn_cube(
         &mut commands,
         &mut meshes,
@@ -71,4 +90,317 @@ pub fn startup(
         },
         Name::new("WorldMiddleBlocker"),
     );
+
+    let ladder_landing = spawn_cube(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        css::BURLYWOOD.into(),
+        Vec2 { x: -450., y: 150. },
+        Vec2 { x: 200., y: 50. },
+        Name::new("WorldLadderLanding"),
+    );
+    commands
+        .entity(ladder_landing)
+        .insert(SurfaceMaterial::Wood);
+
+    spawn_ladder(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        css::SADDLE_BROWN.into(),
+        Vec2 { x: -300., y: -75. },
+        Vec2 { x: 40., y: 500. },
+        Name::new("WorldLadder"),
+    );
+
+    spawn_water(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: -110., y: -280. },
+        Vec2 { x: 300., y: 150. },
+        Name::new("WorldPool"),
+    );
+
+    spawn_grapple_point(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: 400., y: 150. },
+        12.,
+        Name::new("WorldGrapplePoint"),
+    );
+
+    spawn_spikes(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: 200., y: -340. },
+        Vec2 { x: 100., y: 20. },
+        Name::new("WorldSpikes"),
+    );
+
+    spawn_acid(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: -500., y: -345. },
+        Vec2 { x: 100., y: 20. },
+        Name::new("WorldAcid"),
+    );
+
+    spawn_breakable(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: -620., y: -305. },
+        Breakable::new(
+            "acid_wall",
+            Vec2 { x: 40., y: 100. },
+            2,
+            vec![AttackKind::Kick, AttackKind::ChargedShot],
+        ),
+        &persistent_state,
+        Name::new("WorldBreakableWall"),
+    );
+
+    spawn_slope(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: 580., y: -345. },
+        Vec2 { x: 260., y: 40. },
+        25.,
+        Name::new("WorldSlope"),
+    );
+
+    spawn_one_way_platform(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: 350., y: -220. },
+        Vec2 { x: 200., y: 15. },
+        Name::new("WorldOneWayPlatform"),
+    );
+
+    spawn_moving_platform(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: 150., y: 20. },
+        MovingPlatform::new(
+            vec![
+                Vec2::new(250., -60.),
+                Vec2::new(550., -60.),
+                Vec2::new(550., 60.),
+            ],
+            PathMode::PingPong,
+            Easing::InOut,
+            150.,
+            0.6,
+            0.,
+        ),
+        Name::new("WorldMovingPlatform"),
+    );
+
+    spawn_moving_platform(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        Vec2 { x: 160., y: 15. },
+        MovingPlatform::new(
+            vec![Vec2::new(-110., -40.)],
+            PathMode::Once,
+            Easing::Linear,
+            0.,
+            0.,
+            0.5,
+        ),
+        Name::new("WorldSpinningPlatform"),
+    );
+
+    spawn_trigger(
+        &mut commands,
+        Vec2 { x: -110., y: -180. },
+        Vec2 { x: 300., y: 60. },
+        TriggerVolume::new(
+            "pool_surface",
+            COLLIDER | PROJECTILE,
+            None,
+            TriggerMode::Cooldown(1.),
+        ),
+        Name::new("WorldPoolTrigger"),
+    );
+
+    spawn_trigger(
+        &mut commands,
+        Vec2 { x: 500., y: 0. },
+        Vec2 {
+            x: 100.,
+            y: WINDOW_HEIGHT,
+        },
+        TriggerVolume::new("right_side", COLLIDER, Some("player"), TriggerMode::Once),
+        Name::new("WorldRightSideTrigger"),
+    );
+
+    for (x, ability) in [
+        (-550., Ability::DoubleJump),
+        (-500., Ability::Kick),
+        (-450., Ability::Shot),
+        (-400., Ability::Grapple),
+    ] {
+        spawn_ability_pickup(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            Vec2 { x, y: 200. },
+            ability,
+        );
+    }
+}
+
+/// Pushes submerged bodies up against gravity and slows them down.
+pub fn apply_water_forces(
+    mut q_submerged: Query<(&mut LinearVelocity, &Submerged, Option<&GravityScale>)>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    for (mut vel, submerged, o_gravity_scale) in q_submerged.iter_mut() {
+        let Some(water) = submerged.water() else {
+            continue;
+        };
+
+        let gravity_scale = o_gravity_scale.map_or(1., |scale| scale.0);
+        let delta = time.delta_seconds();
+
+        vel.0 -= gravity.0 * gravity_scale * water.buoyancy() * submerged.submersion() * delta;
+        vel.0 *= 1. - (water.drag() * delta).min(1.);
+    }
+}
+
+/// Collision hook for one-way platforms, dropping the contacts they shouldn't be solid for.
+///
+/// A collider is only stopped when it meets the platform from above. Anything that enters from
+/// below or the sides, or drops through on purpose, keeps passing until it's clear of the platform.
+pub fn one_way_platforms(
+    mut q_platform: Query<&mut OneWayPlatform>,
+    q_dropping: Query<(&Body, &DropThrough)>,
+    mut collisions: ResMut<Collisions>,
+) {
+    let dropping: HashSet<Entity> = q_dropping
+        .iter()
+        .filter(|(_, drop_through)| drop_through.check())
+        .map(|(body, _)| body.collider_ref)
+        .collect();
+
+    collisions.retain(|contacts| {
+        let (platform_entity, other, platform_is_first) = if q_platform.contains(contacts.entity1) {
+            (contacts.entity1, contacts.entity2, true)
+        } else if q_platform.contains(contacts.entity2) {
+            (contacts.entity2, contacts.entity1, false)
+        } else {
+            return true;
+        };
+
+        let Ok(mut platform) = q_platform.get_mut(platform_entity) else {
+            return true;
+        };
+
+        if platform.is_passing(other) {
+            let overlapping = contacts.manifolds.iter().any(|manifold| {
+                manifold
+                    .contacts
+                    .iter()
+                    .any(|contact| contact.penetration > 0.)
+            });
+            if overlapping {
+                return false;
+            }
+            platform.stop_passing(other);
+        }
+
+        // The platform's normal points out of it, towards whatever it's touching
+        let from_above = contacts.manifolds.iter().all(|manifold| {
+            let normal = if platform_is_first {
+                manifold.normal1
+            } else {
+                manifold.normal2
+            };
+            normal.y > 0.5
+        });
+
+        if from_above && !dropping.contains(&other) {
+            true
+        } else {
+            platform.start_passing(other);
+            false
+        }
+    });
+}
+
+/// Drives moving platforms along their paths by setting the velocity that gets them there this tick.
+pub fn move_platforms(
+    mut q_platform: Query<(
+        &mut MovingPlatform,
+        &Position,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    if delta <= 0. {
+        return;
+    }
+
+    for (mut platform, position, mut velocity, mut angular_velocity) in q_platform.iter_mut() {
+        let target = platform.advance(time.delta());
+
+        velocity.0 = (target - position.0) / delta;
+        angular_velocity.0 = platform.angular_velocity();
+    }
+}
+
+/// Takes back the platform velocity added last tick, so behaviors work relative to the platform.
+///
+/// Characters that are no longer on the platform keep it instead, that's how jumping off a
+/// moving platform carries its momentum.
+pub fn remove_carried_velocity(
+    mut q_character: Query<(&mut LinearVelocity, &mut Carried, &Grounded)>,
+    q_platform: Query<(), With<MovingPlatform>>,
+) {
+    for (mut velocity, mut carried, grounded) in q_character.iter_mut() {
+        if grounded
+            .ground()
+            .is_some_and(|ground| q_platform.contains(ground.entity()))
+        {
+            velocity.0 -= carried.velocity();
+        }
+        carried.set(Vec2::ZERO);
+    }
+}
+
+/// Adds the velocity of the platform under each character at the point they're standing on.
+pub fn apply_carried_velocity(
+    mut q_character: Query<(&mut LinearVelocity, &mut Carried, &Grounded)>,
+    q_platform: Query<
+        (&Position, &LinearVelocity, &AngularVelocity),
+        (With<MovingPlatform>, Without<Carried>),
+    >,
+) {
+    for (mut velocity, mut carried, grounded) in q_character.iter_mut() {
+        let Some(ground) = grounded.ground() else {
+            continue;
+        };
+        let Ok((position, platform_velocity, angular_velocity)) = q_platform.get(ground.entity())
+        else {
+            continue;
+        };
+
+        // A spinning platform moves faster the further out from its center we stand
+        let arm = ground.point() - position.0;
+        carried.set(platform_velocity.0 + angular_velocity.0 * arm.perp());
+        velocity.0 += carried.velocity();
+    }
 }